@@ -6,6 +6,9 @@ pub(crate) use slice::Slice;
 mod vecbuffer;
 pub(crate) use vecbuffer::VecBuffer;
 
+mod arcbuffer;
+pub(crate) use arcbuffer::ArcBuffer;
+
 mod file;
 pub(crate) use file::File;
 
@@ -13,6 +16,23 @@ pub(crate) use file::File;
 ///
 /// Get values from the ELF file, depending on the header of the ELF file.
 pub(crate) trait BinParser {
+    /// Get a new parser for the same ELF file.
+    ///
+    /// # Returns
+    ///
+    /// If the source of the ELF file can't be duplicated, then `None` is
+    /// returned.
+    fn clone_parser<'elf>(&self) -> Option<Box<dyn BinParser + Send + 'elf>>
+    where
+        Self: 'elf;
+
+    /// Get the length of the ELF file in bytes.
+    ///
+    /// # Returns
+    ///
+    /// If the length can't be determined, then `None` is returned.
+    fn len(&self) -> Option<u64>;
+
     /// Get a single byte at the offset given.
     fn get_u8(&self, offset: u64) -> Option<u8>;
 
@@ -46,6 +66,21 @@ pub(crate) trait BinParser {
     /// If the `offset` is out of range, then `None` is returned.
     fn get_u64(&self, offset: u64, e: Endian) -> Option<u64>;
 
+    /// Read the bytes at the offset given into the buffer.
+    ///
+    /// The default reads each byte with [BinParser::get_u8()]. Parsers that
+    /// read from a file should read the buffer at once.
+    ///
+    /// # Returns
+    ///
+    /// If any byte of the buffer is out of range, then `None` is returned.
+    fn read_into(&self, offset: u64, buffer: &mut [u8]) -> Option<()> {
+        for (i, b) in buffer.iter_mut().enumerate() {
+            *b = self.get_u8(offset.checked_add(i as u64)?)?;
+        }
+        Some(())
+    }
+
     /// Get a "native" bit value at the offset given.
     ///
     /// The bytes are swapped as necessary depending on [Endian] of the ELF
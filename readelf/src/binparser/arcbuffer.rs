@@ -0,0 +1,46 @@
+use super::{BinParser, Endian, Slice};
+use std::sync::Arc;
+
+pub(crate) struct ArcBuffer {
+    buffer: Arc<[u8]>,
+}
+
+impl ArcBuffer {
+    pub(crate) fn new(buffer: Arc<[u8]>) -> ArcBuffer {
+        ArcBuffer { buffer }
+    }
+}
+
+impl BinParser for ArcBuffer {
+    fn clone_parser<'elf>(&self) -> Option<Box<dyn BinParser + Send + 'elf>> {
+        Some(Box::new(ArcBuffer::new(Arc::clone(&self.buffer))))
+    }
+
+    fn len(&self) -> Option<u64> {
+        Some(self.buffer.len() as u64)
+    }
+
+    #[inline(always)]
+    fn get_u8(&self, offset: u64) -> Option<u8> {
+        let slice = Slice::new(&self.buffer);
+        slice.get_u8(offset)
+    }
+
+    #[inline(always)]
+    fn get_u16(&self, offset: u64, e: Endian) -> Option<u16> {
+        let slice = Slice::new(&self.buffer);
+        slice.get_u16(offset, e)
+    }
+
+    #[inline(always)]
+    fn get_u32(&self, offset: u64, e: Endian) -> Option<u32> {
+        let slice = Slice::new(&self.buffer);
+        slice.get_u32(offset, e)
+    }
+
+    #[inline(always)]
+    fn get_u64(&self, offset: u64, e: Endian) -> Option<u64> {
+        let slice = Slice::new(&self.buffer);
+        slice.get_u64(offset, e)
+    }
+}
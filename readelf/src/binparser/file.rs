@@ -1,40 +1,85 @@
 use super::{BinParser, Endian};
-use std::cell::RefCell;
-use std::io::*;
+use std::io::Result;
 use std::path::Path;
 
+/// Read an ELF file from disk.
+///
+/// All reads are positional, so that the handle of the file can be shared by
+/// clones of the parser, also in other threads, without a shared position in
+/// the file.
 pub(crate) struct File {
-    elf: RefCell<std::fs::File>, // A file must be mutable.
+    elf: std::fs::File,
 }
 
 impl File {
-    pub(crate) fn open<P: AsRef<Path>>(path: P) -> Option<File> {
-        let elf_file = std::fs::File::open(path).ok()?;
-        Some(File {
-            elf: RefCell::new(elf_file),
-        })
+    pub(crate) fn open<P: AsRef<Path>>(path: P) -> Result<File> {
+        let elf = std::fs::File::open(path.as_ref())?;
+        Ok(File { elf })
+    }
+
+    /// Read the bytes at the offset into the buffer, without changing the
+    /// position of the file.
+    #[cfg(unix)]
+    fn read_at(&self, offset: u64, buffer: &mut [u8]) -> Option<()> {
+        use std::os::unix::fs::FileExt;
+        self.elf.read_exact_at(buffer, offset).ok()
+    }
+
+    /// Read the bytes at the offset into the buffer, from any position of the
+    /// file.
+    #[cfg(windows)]
+    fn read_at(&self, offset: u64, buffer: &mut [u8]) -> Option<()> {
+        use std::os::windows::fs::FileExt;
+        let mut read = 0;
+        while read < buffer.len() {
+            let pos = offset.checked_add(read as u64)?;
+            match self.elf.seek_read(&mut buffer[read..], pos) {
+                Ok(0) => return None,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(_) => return None,
+            }
+        }
+        Some(())
+    }
+
+    /// Read the bytes at the offset into the buffer, seeking in the file.
+    #[cfg(not(any(unix, windows)))]
+    fn read_at(&self, offset: u64, buffer: &mut [u8]) -> Option<()> {
+        use std::io::{Read, Seek, SeekFrom};
+        let mut elf = &self.elf;
+        elf.seek(SeekFrom::Start(offset)).ok()?;
+        elf.read_exact(buffer).ok()
     }
 }
 
 impl BinParser for File {
+    fn clone_parser<'elf>(&self) -> Option<Box<dyn BinParser + Send + 'elf>> {
+        // Duplicate the handle, instead of opening the path again, which may
+        // be a different file by now. Without positional reads, the position
+        // would be shared.
+        if cfg!(not(any(unix, windows))) {
+            return None;
+        }
+        let elf = self.elf.try_clone().ok()?;
+        Some(Box::new(File { elf }))
+    }
+
+    fn len(&self) -> Option<u64> {
+        Some(self.elf.metadata().ok()?.len())
+    }
+
     #[inline(always)]
     fn get_u8(&self, offset: u64) -> Option<u8> {
-        let mut elf = self.elf.borrow_mut();
-        elf.seek(SeekFrom::Start(offset)).ok()?;
-
         let mut buff = [0; 1];
-        elf.read_exact(&mut buff).ok()?;
-
+        self.read_at(offset, &mut buff)?;
         Some(buff[0])
     }
 
     #[inline(always)]
     fn get_u16(&self, offset: u64, e: Endian) -> Option<u16> {
-        let mut elf = self.elf.borrow_mut();
-        elf.seek(SeekFrom::Start(offset)).ok()?;
-
         let mut buff = [0; 2];
-        elf.read_exact(&mut buff).ok()?;
+        self.read_at(offset, &mut buff)?;
 
         match e {
             Endian::Little => Some(u16::from_le_bytes(buff)),
@@ -44,11 +89,8 @@ impl BinParser for File {
 
     #[inline(always)]
     fn get_u32(&self, offset: u64, e: Endian) -> Option<u32> {
-        let mut elf = self.elf.borrow_mut();
-        elf.seek(SeekFrom::Start(offset)).ok()?;
-
         let mut buff = [0; 4];
-        elf.read_exact(&mut buff).ok()?;
+        self.read_at(offset, &mut buff)?;
 
         match e {
             Endian::Little => Some(u32::from_le_bytes(buff)),
@@ -58,17 +100,18 @@ impl BinParser for File {
 
     #[inline(always)]
     fn get_u64(&self, offset: u64, e: Endian) -> Option<u64> {
-        let mut elf = self.elf.borrow_mut();
-        elf.seek(SeekFrom::Start(offset)).ok()?;
-
         let mut buff = [0; 8];
-        elf.read_exact(&mut buff).ok()?;
+        self.read_at(offset, &mut buff)?;
 
         match e {
             Endian::Little => Some(u64::from_le_bytes(buff)),
             Endian::Big => Some(u64::from_be_bytes(buff)),
         }
     }
+
+    fn read_into(&self, offset: u64, buffer: &mut [u8]) -> Option<()> {
+        self.read_at(offset, buffer)
+    }
 }
 
 #[cfg(test)]
@@ -159,4 +202,32 @@ mod tests {
         assert_eq!(buffer.get_u64(u64::MAX, Endian::Big), None);
         assert_eq!(buffer.get_u64(u64::MAX - 1, Endian::Big), None);
     }
+
+    #[test]
+    fn test_read_into() {
+        let buffer = File::open(test_resource_path("elf/debian-9.13.0-i386-netinst/bash")).unwrap();
+        let mut bytes = [0; 4];
+        assert_eq!(buffer.read_into(0, &mut bytes), Some(()));
+        assert_eq!(bytes, [0x7f, 0x45, 0x4c, 0x46]);
+        assert_eq!(buffer.read_into(60, &mut bytes), Some(()));
+        assert_eq!(buffer.read_into(61, &mut bytes), None);
+        assert_eq!(buffer.read_into(u64::MAX, &mut bytes), None);
+        assert_eq!(buffer.read_into(64, &mut []), Some(()));
+    }
+
+    #[test]
+    fn test_clone_parser_same_file() {
+        // The clone reads the same file, even if the path is replaced.
+        let path = std::env::temp_dir().join(format!("readelf-clone-{}", std::process::id()));
+        std::fs::write(&path, [1, 2, 3, 4]).unwrap();
+        let buffer = File::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::write(&path, [5, 6]).unwrap();
+
+        let clone = buffer.clone_parser().unwrap();
+        assert_eq!(clone.len(), Some(4));
+        assert_eq!(clone.get_u8(3), Some(4));
+        assert_eq!(buffer.get_u16(0, Endian::Big), Some(0x0102));
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -12,6 +12,17 @@ impl<'elf> Slice<'elf> {
 }
 
 impl<'elf> BinParser for Slice<'elf> {
+    fn clone_parser<'a>(&self) -> Option<Box<dyn BinParser + Send + 'a>>
+    where
+        Self: 'a,
+    {
+        Some(Box::new(Slice::new(self.buffer)))
+    }
+
+    fn len(&self) -> Option<u64> {
+        Some(self.buffer.len() as u64)
+    }
+
     #[inline(always)]
     fn get_u8(&self, offset: u64) -> Option<u8> {
         if self.buffer.len() < std::mem::size_of::<u8>() || offset >= self.buffer.len() as u64 {
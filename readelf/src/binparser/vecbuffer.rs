@@ -12,6 +12,14 @@ impl VecBuffer {
 }
 
 impl BinParser for VecBuffer {
+    fn clone_parser<'elf>(&self) -> Option<Box<dyn BinParser + Send + 'elf>> {
+        Some(Box::new(VecBuffer::new(self.buffer.clone())))
+    }
+
+    fn len(&self) -> Option<u64> {
+        Some(self.buffer.len() as u64)
+    }
+
     #[inline(always)]
     fn get_u8(&self, offset: u64) -> Option<u8> {
         let slice = Slice::new(self.buffer.as_slice());
@@ -25,7 +25,7 @@ use std::fmt;
 /// let v: u8 = e.into();
 /// println!("32-bit has value {}", v);
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Class {
     /// 32-bit format.
     ///
@@ -0,0 +1,374 @@
+use crate::{Class, Endian, LdSoCache, Machine, ReadElf};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Where to search for shared objects, when resolving the dependencies with
+/// [resolve_dependencies()].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::{resolve_dependencies, LdSoCache, SearchConfig};
+///
+/// let config = SearchConfig {
+///     library_path: std::env::var_os("LD_LIBRARY_PATH")
+///         .map(|p| std::env::split_paths(&p).collect())
+///         .unwrap_or_default(),
+///     cache: LdSoCache::open("/etc/ld.so.cache"),
+///     ..Default::default()
+/// };
+/// for dep in resolve_dependencies("/bin/ls", &config).unwrap() {
+///     println!("{} => {:?}", dep.name, dep.path);
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchConfig {
+    /// The directories searched after `DT_RPATH` and before `DT_RUNPATH`, as
+    /// given by the environment variable `LD_LIBRARY_PATH`.
+    pub library_path: Vec<PathBuf>,
+
+    /// The cache of the dynamic loader, searched after `DT_RUNPATH`, unless
+    /// the object depending on the library has the flag `DF_1_NODEFLIB`.
+    pub cache: Option<LdSoCache>,
+
+    /// The directories searched last, unless the object depending on the
+    /// library has the flag `DF_1_NODEFLIB`.
+    ///
+    /// The default is `/lib`, `/usr/lib`, `/lib64` and `/usr/lib64`. Use
+    /// [SearchConfig::for_target()] for the directories of the architecture
+    /// of an ELF file.
+    pub default_dirs: Vec<PathBuf>,
+
+    /// The root directory of the target system, for cross-compiled files.
+    ///
+    /// The absolute paths of the search directories, the cache and the
+    /// dependencies are paths on the target, and are prefixed with the
+    /// sysroot. Paths relative to `$ORIGIN` are not, as they are relative to
+    /// the file being resolved.
+    pub sysroot: Option<PathBuf>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            library_path: Vec::new(),
+            cache: None,
+            default_dirs: ["/lib", "/usr/lib", "/lib64", "/usr/lib64"]
+                .iter()
+                .map(PathBuf::from)
+                .collect(),
+            sysroot: None,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Get the search configuration with the default directories for the
+    /// architecture of the ELF file.
+    ///
+    /// The directories are those of the multiarch layout of Debian for the
+    /// [Machine], [Class], [Endian] and the ABI (e.g. `/lib/aarch64-linux-gnu`),
+    /// followed by `/lib64` and `/usr/lib64` for 64-bit files, and `/lib` and
+    /// `/usr/lib`. Set [SearchConfig::sysroot] to resolve against the
+    /// libraries of a cross-compiled target.
+    pub fn for_target(elf: &ReadElf<'_>) -> SearchConfig {
+        let mut dirs = Vec::new();
+        if let Some(triplet) = multiarch(elf) {
+            dirs.push(Path::new("/lib").join(triplet));
+            dirs.push(Path::new("/usr/lib").join(triplet));
+        }
+        if elf.class == Class::Elf64 {
+            dirs.push(PathBuf::from("/lib64"));
+            dirs.push(PathBuf::from("/usr/lib64"));
+        }
+        dirs.push(PathBuf::from("/lib"));
+        dirs.push(PathBuf::from("/usr/lib"));
+        SearchConfig {
+            default_dirs: dirs,
+            ..Default::default()
+        }
+    }
+}
+
+/// Get the multiarch triplet of Debian for the architecture of the ELF file.
+fn multiarch(elf: &ReadElf<'_>) -> Option<&'static str> {
+    const EF_ARM_ABI_FLOAT_HARD: u32 = 0x400;
+
+    let elf64 = elf.class == Class::Elf64;
+    let little = elf.data == Endian::Little;
+    let triplet = match u16::from(elf.machine) {
+        Machine::X86_64 if elf64 => "x86_64-linux-gnu",
+        Machine::X86_64 => "x86_64-linux-gnux32",
+        Machine::INTEL_386 => "i386-linux-gnu",
+        Machine::AARCH64 if little => "aarch64-linux-gnu",
+        Machine::AARCH64 => "aarch64_be-linux-gnu",
+        Machine::ARM if elf.flags & EF_ARM_ABI_FLOAT_HARD != 0 => "arm-linux-gnueabihf",
+        Machine::ARM => "arm-linux-gnueabi",
+        Machine::RISCV if elf64 => "riscv64-linux-gnu",
+        Machine::PPC64 if little => "powerpc64le-linux-gnu",
+        Machine::PPC64 => "powerpc64-linux-gnu",
+        Machine::PPC => "powerpc-linux-gnu",
+        Machine::S390 if elf64 => "s390x-linux-gnu",
+        Machine::MIPS if elf64 && little => "mips64el-linux-gnuabi64",
+        Machine::MIPS if elf64 => "mips64-linux-gnuabi64",
+        Machine::MIPS if little => "mipsel-linux-gnu",
+        Machine::MIPS => "mips-linux-gnu",
+        Machine::LOONGARCH if elf64 => "loongarch64-linux-gnu",
+        Machine::SPARCV9 => "sparc64-linux-gnu",
+        Machine::ALPHA => "alpha-linux-gnu",
+        Machine::MOTOROLA_68K => "m68k-linux-gnu",
+        Machine::PARISC => "hppa-linux-gnu",
+        Machine::SH => "sh4-linux-gnu",
+        _ => return None,
+    };
+    Some(triplet)
+}
+
+/// A shared object that an ELF file depends on, from a `DT_NEEDED` entry.
+///
+/// Get the dependencies with [resolve_dependencies()].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dependency {
+    /// The name of the shared object, as given in `DT_NEEDED`.
+    pub name: String,
+
+    /// The path of the shared object, or [Option::None] if it isn't found.
+    pub path: Option<PathBuf>,
+
+    /// The dependencies of the shared object.
+    ///
+    /// A shared object is only resolved the first time it's needed, later
+    /// entries have no dependencies, as the dynamic loader uses the object
+    /// already loaded.
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Dependency {
+    /// Check if the shared object and all its dependencies are found.
+    pub fn is_resolved(&self) -> bool {
+        self.path.is_some() && self.dependencies.iter().all(Dependency::is_resolved)
+    }
+}
+
+/// Resolve the tree of shared objects that an ELF file depends on, similar to
+/// `ldd`, but without running the dynamic loader.
+///
+/// The `DT_NEEDED` entries of each object are searched for like the GNU
+/// dynamic loader does:
+///
+/// - a name with a `/` is a path, relative to the current directory;
+/// - the `DT_RPATH` of the object and the objects that loaded it, if the
+///   object has no `DT_RUNPATH`;
+/// - [SearchConfig::library_path];
+/// - the `DT_RUNPATH` of the object;
+/// - [SearchConfig::cache] and [SearchConfig::default_dirs], if the object
+///   doesn't have the flag `DF_1_NODEFLIB`.
+///
+/// The token `$ORIGIN` in a search path is replaced with the directory of the
+/// object. Other absolute paths are prefixed with [SearchConfig::sysroot]. A
+/// file is only used if it's an ELF file with the same class,
+/// endianness and machine as the file at `path`. The program interpreter is
+/// not part of the dependencies.
+///
+/// # Returns
+///
+/// Returns the dependencies of the file at `path`, or [Option::None] if it
+/// can't be read as an ELF file.
+pub fn resolve_dependencies<P: AsRef<Path>>(
+    path: P,
+    config: &SearchConfig,
+) -> Option<Vec<Dependency>> {
+    let elf = ReadElf::open(path.as_ref())?;
+    let mut resolver = Resolver {
+        config,
+        class: elf.class,
+        data: elf.data,
+        machine: elf.machine,
+        loaded: HashMap::new(),
+    };
+    let object = Object::new(&elf, path.as_ref(), config.sysroot.as_deref());
+    Some(resolver.resolve(&object, &object.rpath))
+}
+
+/// The dynamic information of an object needed to resolve its dependencies.
+struct Object {
+    origin: PathBuf,
+    needed: Vec<String>,
+    rpath: Vec<PathBuf>,
+    runpath: Option<Vec<PathBuf>>,
+    no_default_lib: bool,
+}
+
+impl Object {
+    fn new(elf: &ReadElf<'_>, path: &Path, sysroot: Option<&Path>) -> Object {
+        const DT_FLAGS_1: u64 = 0x6FFFFFFB;
+        const DF_1_NODEFLIB: u64 = 0x800;
+
+        let origin = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let runpath = elf.runpath().map(|p| search_path(&p, &origin, sysroot));
+
+        // The dynamic loader ignores `DT_RPATH` if there is a `DT_RUNPATH`.
+        let rpath = match (&runpath, elf.rpath()) {
+            (None, Some(p)) => search_path(&p, &origin, sysroot),
+            _ => Vec::new(),
+        };
+        Object {
+            needed: elf.needed_libraries(),
+            rpath,
+            runpath,
+            no_default_lib: elf
+                .dynamic_entries()
+                .iter()
+                .any(|(t, v)| *t == DT_FLAGS_1 && v & DF_1_NODEFLIB != 0),
+            origin,
+        }
+    }
+}
+
+struct Resolver<'c> {
+    config: &'c SearchConfig,
+    class: Class,
+    data: Endian,
+    machine: Machine,
+    loaded: HashMap<String, Option<PathBuf>>,
+}
+
+impl Resolver<'_> {
+    /// Resolve the dependencies of the object, with the `DT_RPATH` of the
+    /// object and the objects that loaded it.
+    fn resolve(&mut self, object: &Object, rpath: &[PathBuf]) -> Vec<Dependency> {
+        let mut dependencies = Vec::new();
+        for name in &object.needed {
+            if let Some(path) = self.loaded.get(name) {
+                dependencies.push(Dependency {
+                    name: name.clone(),
+                    path: path.clone(),
+                    dependencies: Vec::new(),
+                });
+                continue;
+            }
+
+            let found = self.search(name, object, rpath);
+            self.loaded
+                .insert(name.clone(), found.as_ref().map(|(p, _)| p.clone()));
+            let (path, children) = match found {
+                Some((path, child)) => {
+                    let mut child_rpath = child.rpath.clone();
+                    child_rpath.extend_from_slice(rpath);
+                    let children = self.resolve(&child, &child_rpath);
+                    (Some(path), children)
+                }
+                None => (None, Vec::new()),
+            };
+            dependencies.push(Dependency {
+                name: name.clone(),
+                path,
+                dependencies: children,
+            });
+        }
+        dependencies
+    }
+
+    fn search(&self, name: &str, object: &Object, rpath: &[PathBuf]) -> Option<(PathBuf, Object)> {
+        let sysroot = self.config.sysroot.as_deref();
+        if name.contains('/') {
+            let path = target_path(name, &object.origin, sysroot);
+            return self.load(&path).map(|o| (path, o));
+        }
+
+        let rpath = match object.runpath {
+            None => rpath,
+            Some(_) => &[],
+        };
+        let library_path = self
+            .config
+            .library_path
+            .iter()
+            .map(|dir| in_sysroot(dir, sysroot));
+        let dirs = rpath
+            .iter()
+            .cloned()
+            .chain(library_path)
+            .chain(object.runpath.iter().flatten().cloned())
+            .map(|dir| dir.join(name));
+        let default_dirs = self.config.default_dirs.iter().map(|dir| dir.join(name));
+        let system = self
+            .config
+            .cache
+            .iter()
+            .flat_map(|c| c.find(name).map(Path::to_path_buf))
+            .chain(default_dirs)
+            .map(|path| in_sysroot(&path, sysroot))
+            .filter(|_| !object.no_default_lib);
+        dirs.chain(system)
+            .find_map(|path| self.load(&path).map(|o| (path, o)))
+    }
+
+    /// Read the object, if it's compatible with the ELF file being resolved.
+    fn load(&self, path: &Path) -> Option<Object> {
+        let elf = ReadElf::open(path)?;
+        if elf.class != self.class || elf.data != self.data || elf.machine != self.machine {
+            return None;
+        }
+        Some(Object::new(&elf, path, self.config.sysroot.as_deref()))
+    }
+}
+
+/// Split a colon separated search path into the paths of the directories.
+///
+/// An empty directory is the current directory.
+fn search_path(path: &str, origin: &Path, sysroot: Option<&Path>) -> Vec<PathBuf> {
+    path.split(':')
+        .map(|dir| match dir {
+            "" => PathBuf::from("."),
+            dir => target_path(dir, origin, sysroot),
+        })
+        .collect()
+}
+
+/// Get the path of a path on the target, replacing `$ORIGIN`, or prefixing
+/// the sysroot.
+fn target_path(path: &str, origin: &Path, sysroot: Option<&Path>) -> PathBuf {
+    if path.contains("$ORIGIN") || path.contains("${ORIGIN}") {
+        let origin = origin.to_string_lossy();
+        let path = path
+            .replace("${ORIGIN}", &origin)
+            .replace("$ORIGIN", &origin);
+        return PathBuf::from(path);
+    }
+    in_sysroot(Path::new(path), sysroot)
+}
+
+/// Prefix an absolute path with the sysroot.
+fn in_sysroot(path: &Path, sysroot: Option<&Path>) -> PathBuf {
+    match (sysroot, path.strip_prefix("/")) {
+        (Some(sysroot), Ok(relative)) => sysroot.join(relative),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::search_path;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn search_path_origin() {
+        assert_eq!(
+            search_path(
+                "$ORIGIN/../lib:/opt/lib::${ORIGIN}",
+                Path::new("/app/bin"),
+                None
+            ),
+            vec![
+                PathBuf::from("/app/bin/../lib"),
+                PathBuf::from("/opt/lib"),
+                PathBuf::from("."),
+                PathBuf::from("/app/bin"),
+            ]
+        );
+    }
+}
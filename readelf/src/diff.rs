@@ -0,0 +1,365 @@
+use crate::{Class, Endian, ExecutableType, Machine, OsAbi, ParseError, ProgramHeader, ReadElf};
+use crate::{SegmentFlags, SegmentType};
+
+/// A field of the ELF header that differs between two files.
+///
+/// Each variant contains the value of the first (old) file, followed by the
+/// value of the second (new) file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HeaderChange {
+    /// The class differs, `e_ident[EI_CLASS]`.
+    Class(Class, Class),
+
+    /// The endianness differs, `e_ident[EI_DATA]`.
+    Data(Endian, Endian),
+
+    /// The version differs, `e_version`.
+    Version(u32, u32),
+
+    /// The OS ABI differs, `e_ident[EI_OSABI]`.
+    OsAbi(OsAbi, OsAbi),
+
+    /// The ABI version differs, `e_ident[EI_ABIVERSION]`.
+    AbiVersion(u8, u8),
+
+    /// The object file type differs, `e_type`.
+    ExecutableType(ExecutableType, ExecutableType),
+
+    /// The machine differs, `e_machine`.
+    Machine(Machine, Machine),
+
+    /// The entry point differs, `e_entry`.
+    Entry(u64, u64),
+
+    /// The architecture specific flags differ, `e_flags`.
+    Flags(u32, u32),
+
+    /// The size of the ELF header differs, `e_ehsize`.
+    FileHeaderSize(u16, u16),
+
+    /// The offset of the program header table differs, `e_phoff`.
+    ProgramHeaderOffset(u64, u64),
+
+    /// The size of a program header entry differs, `e_phentsize`.
+    ProgramHeaderSize(u16, u16),
+
+    /// The number of program headers differs, `e_phnum`.
+    ProgramHeaderCount(u16, u16),
+
+    /// The offset of the section header table differs, `e_shoff`.
+    SectionHeaderOffset(u64, u64),
+
+    /// The size of a section header entry differs, `e_shentsize`.
+    SectionHeaderSize(u16, u16),
+
+    /// The number of section headers differs, `e_shnum`.
+    SectionHeaderCount(u16, u16),
+
+    /// The index of the section name string table differs, `e_shstrndx`.
+    StringSectionIndex(u16, u16),
+}
+
+/// A segment that differs between two files.
+///
+/// Segments are paired by their [SegmentType], in the order they appear in the
+/// program header table. That is, the second [SegmentType::Load] of the first
+/// file is compared against the second [SegmentType::Load] of the second file.
+/// The index is the position in the program header table of the respective
+/// file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SegmentChange {
+    /// The segment only exists in the second file.
+    Added {
+        /// Index of the segment in the second file.
+        index: usize,
+
+        /// The segment that was added.
+        segment: ProgramHeader,
+    },
+
+    /// The segment only exists in the first file.
+    Removed {
+        /// Index of the segment in the first file.
+        index: usize,
+
+        /// The segment that was removed.
+        segment: ProgramHeader,
+    },
+
+    /// The file size or memory size of the segment differs.
+    Resized {
+        /// Index of the segment in the first file.
+        old_index: usize,
+
+        /// Index of the segment in the second file.
+        new_index: usize,
+
+        /// The segment type of the segments being compared.
+        segment_type: SegmentType,
+
+        /// The file size and memory size of the segment in the first file.
+        old_size: (u64, u64),
+
+        /// The file size and memory size of the segment in the second file.
+        new_size: (u64, u64),
+    },
+
+    /// The file offset or the virtual address of the segment differs.
+    Moved {
+        /// Index of the segment in the first file.
+        old_index: usize,
+
+        /// Index of the segment in the second file.
+        new_index: usize,
+
+        /// The segment type of the segments being compared.
+        segment_type: SegmentType,
+
+        /// The file offset and virtual address in the first file.
+        old_location: (u64, u64),
+
+        /// The file offset and virtual address in the second file.
+        new_location: (u64, u64),
+    },
+
+    /// The physical address of the segment differs.
+    PhysicalAddress {
+        /// Index of the segment in the first file.
+        old_index: usize,
+
+        /// Index of the segment in the second file.
+        new_index: usize,
+
+        /// The segment type of the segments being compared.
+        segment_type: SegmentType,
+
+        /// The physical address in the first file.
+        old_address: u64,
+
+        /// The physical address in the second file.
+        new_address: u64,
+    },
+
+    /// The flags of the segment differ.
+    Flags {
+        /// Index of the segment in the first file.
+        old_index: usize,
+
+        /// Index of the segment in the second file.
+        new_index: usize,
+
+        /// The segment type of the segments being compared.
+        segment_type: SegmentType,
+
+        /// The flags of the segment in the first file.
+        old_flags: SegmentFlags,
+
+        /// The flags of the segment in the second file.
+        new_flags: SegmentFlags,
+    },
+
+    /// The alignment of the segment differs.
+    Alignment {
+        /// Index of the segment in the first file.
+        old_index: usize,
+
+        /// Index of the segment in the second file.
+        new_index: usize,
+
+        /// The segment type of the segments being compared.
+        segment_type: SegmentType,
+
+        /// The alignment of the segment in the first file.
+        old_alignment: u64,
+
+        /// The alignment of the segment in the second file.
+        new_alignment: u64,
+    },
+}
+
+/// The structural differences between two ELF files.
+///
+/// Get an instance with [diff()]. The differences are listed in the order of
+/// the fields in the ELF header, followed by the order of the program headers.
+///
+/// Sections are not compared individually, only the section header table
+/// properties given by the ELF header.
+#[derive(Debug, Default, PartialEq)]
+pub struct Diff {
+    /// Fields in the ELF header that differ.
+    pub header: Vec<HeaderChange>,
+
+    /// Segments that were added, removed or changed.
+    pub segments: Vec<SegmentChange>,
+
+    /// The reason why the program header table of the first file can't be
+    /// read completely. The segments after the one that can't be read are not
+    /// compared.
+    pub old_segment_error: Option<ParseError>,
+
+    /// The reason why the program header table of the second file can't be
+    /// read completely.
+    pub new_segment_error: Option<ParseError>,
+}
+
+impl Diff {
+    /// Check if there are no structural differences between the two files.
+    ///
+    /// If a program header table can't be read completely, the files are not
+    /// known to be the same, and this returns `false`.
+    pub fn is_empty(&self) -> bool {
+        self.header.is_empty()
+            && self.segments.is_empty()
+            && self.old_segment_error.is_none()
+            && self.new_segment_error.is_none()
+    }
+}
+
+/// Compare the structure of two ELF files.
+///
+/// The file `a` is considered the old file, and `b` the new file. Only
+/// structural differences are reported, not differences in the contents of the
+/// segments.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::ReadElf;
+///
+/// let a = ReadElf::open("old/libfoo.so").unwrap();
+/// let b = ReadElf::open("new/libfoo.so").unwrap();
+/// let d = readelf::diff(&a, &b);
+/// for change in d.header {
+///     println!("{:?}", change);
+/// }
+/// ```
+pub fn diff(a: &ReadElf<'_>, b: &ReadElf<'_>) -> Diff {
+    let (old, old_segment_error) = read_segments(a);
+    let (new, new_segment_error) = read_segments(b);
+    Diff {
+        header: diff_header(a, b),
+        segments: diff_segments(old, new),
+        old_segment_error,
+        new_segment_error,
+    }
+}
+
+fn diff_header(a: &ReadElf<'_>, b: &ReadElf<'_>) -> Vec<HeaderChange> {
+    let mut changes = Vec::new();
+
+    macro_rules! compare {
+        ($field:ident, $variant:ident) => {
+            if a.$field != b.$field {
+                changes.push(HeaderChange::$variant(a.$field, b.$field));
+            }
+        };
+    }
+
+    compare!(class, Class);
+    compare!(data, Data);
+    compare!(version, Version);
+    compare!(osabi, OsAbi);
+    compare!(abi_version, AbiVersion);
+    compare!(exec_type, ExecutableType);
+    compare!(machine, Machine);
+    compare!(entry, Entry);
+    compare!(flags, Flags);
+    compare!(file_header_size, FileHeaderSize);
+    compare!(program_header_offset, ProgramHeaderOffset);
+    compare!(program_header_size, ProgramHeaderSize);
+    compare!(program_header_count, ProgramHeaderCount);
+    compare!(section_header_offset, SectionHeaderOffset);
+    compare!(section_header_size, SectionHeaderSize);
+    compare!(section_header_count, SectionHeaderCount);
+    compare!(string_section_index, StringSectionIndex);
+    changes
+}
+
+/// Read the program headers with their index, up to the first one that can't
+/// be read.
+fn read_segments(elf: &ReadElf<'_>) -> (Vec<(usize, ProgramHeader)>, Option<ParseError>) {
+    let mut segments = Vec::new();
+    for (index, ph) in elf.program_headers().iter_checked().enumerate() {
+        match ph {
+            Ok(ph) => segments.push((index, ph)),
+            Err(e) => return (segments, Some(e)),
+        }
+    }
+    (segments, None)
+}
+
+fn diff_segments(
+    mut old: Vec<(usize, ProgramHeader)>,
+    mut new: Vec<(usize, ProgramHeader)>,
+) -> Vec<SegmentChange> {
+    let mut changes = Vec::new();
+
+    // Pair the segments with the same type in the order they occur. Anything
+    // that isn't paired is removed (in `old`) or added (in `new`).
+    let mut pairs = Vec::new();
+    let mut o = 0;
+    while o < old.len() {
+        let segment_type = old[o].1.segment_type;
+        match new.iter().position(|(_, s)| s.is_type(segment_type)) {
+            Some(n) => pairs.push((old.remove(o), new.remove(n))),
+            None => o += 1,
+        }
+    }
+
+    for ((old_index, os), (new_index, ns)) in pairs {
+        let segment_type = os.segment_type;
+        if os.file_size != ns.file_size || os.memory_size != ns.memory_size {
+            changes.push(SegmentChange::Resized {
+                old_index,
+                new_index,
+                segment_type,
+                old_size: (os.file_size, os.memory_size),
+                new_size: (ns.file_size, ns.memory_size),
+            });
+        }
+        if os.file_offset != ns.file_offset || os.virtual_address != ns.virtual_address {
+            changes.push(SegmentChange::Moved {
+                old_index,
+                new_index,
+                segment_type,
+                old_location: (os.file_offset, os.virtual_address),
+                new_location: (ns.file_offset, ns.virtual_address),
+            });
+        }
+        if os.physical_address != ns.physical_address {
+            changes.push(SegmentChange::PhysicalAddress {
+                old_index,
+                new_index,
+                segment_type,
+                old_address: os.physical_address,
+                new_address: ns.physical_address,
+            });
+        }
+        if os.flags != ns.flags {
+            changes.push(SegmentChange::Flags {
+                old_index,
+                new_index,
+                segment_type,
+                old_flags: os.flags,
+                new_flags: ns.flags,
+            });
+        }
+        if os.alignment != ns.alignment {
+            changes.push(SegmentChange::Alignment {
+                old_index,
+                new_index,
+                segment_type,
+                old_alignment: os.alignment,
+                new_alignment: ns.alignment,
+            });
+        }
+    }
+
+    for (index, segment) in old {
+        changes.push(SegmentChange::Removed { index, segment });
+    }
+    for (index, segment) in new {
+        changes.push(SegmentChange::Added { index, segment });
+    }
+    changes
+}
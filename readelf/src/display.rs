@@ -0,0 +1,877 @@
+//! Format the contents of an ELF file as text.
+//!
+//! The output follows the layout of GNU `readelf`, so that tools built on this
+//! library can produce the same reports. Only the information that can be read
+//! by this library is printed, e.g. the mapping of sections to segments is not
+//! shown.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use readelf::{display, ReadElf};
+//!
+//! let elf = ReadElf::open("/bin/sh").unwrap();
+//! print!("{}", display::format_file_header(&elf));
+//! print!("{}", display::format_program_headers(&elf));
+//! ```
+
+use crate::{
+    Class, Diff, Endian, ExecutableType, HeaderChange, Machine, OsAbi, ProgramHeader, ReadElf,
+    SegmentChange, SegmentFlags, SegmentType, SizeReport,
+};
+use std::fmt::Write;
+
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Options controlling how the text is formatted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    /// Don't wrap lines to fit in 80 columns, like `readelf -W`.
+    pub wide: bool,
+
+    /// Use the same wording as GNU `readelf` for the OS ABI and the machine.
+    ///
+    /// Otherwise the names of [OsAbi] and [Machine] are used. Machines that
+    /// are not common use their name from [Machine] in any case.
+    pub gnu: bool,
+
+    /// Colour the output with ANSI escape sequences.
+    ///
+    /// Headings are bold, and the flags of segments are coloured. Segments
+    /// that are writable and executable, and alignments that don't match
+    /// the offset and address of the segment, are shown in red.
+    pub color: bool,
+}
+
+impl Options {
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.color {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn flags(&self, flags: SegmentFlags) -> String {
+        let text = segment_flags(flags);
+        if !self.color {
+            return text;
+        }
+
+        if flags.is_writable() && flags.is_executable() {
+            return self.paint(&text, RED);
+        }
+        text.chars()
+            .map(|c| match c {
+                'R' => self.paint("R", GREEN),
+                'W' => self.paint("W", YELLOW),
+                'E' => self.paint("E", CYAN),
+                c => c.to_string(),
+            })
+            .collect()
+    }
+
+    fn alignment(&self, ph: &ProgramHeader) -> String {
+        let text = format!("{:#x}", ph.alignment);
+        if ph.is_aligned() {
+            text
+        } else {
+            self.paint(&text, RED)
+        }
+    }
+}
+
+/// Format the ELF header, like `readelf -h`.
+pub fn format_file_header(elf: &ReadElf<'_>) -> String {
+    format_file_header_with(elf, &Options::default())
+}
+
+/// Format the ELF header, like `readelf -h`, with the [Options] given.
+pub fn format_file_header_with(elf: &ReadElf<'_>, options: &Options) -> String {
+    let raw = elf.raw_header();
+    let mut out = String::new();
+
+    // Writing to a `String` never fails.
+    let _ = writeln!(out, "{}", options.paint("ELF Header:", BOLD));
+    let ident = elf.ident().unwrap_or_default();
+    let _ = write!(out, "  Magic:  ");
+    for b in ident {
+        let _ = write!(out, " {:02x}", b);
+    }
+    let _ = writeln!(out, " ");
+    field(&mut out, "Class:", class(elf.class));
+    field(&mut out, "Data:", data(elf.data));
+    let version = match ident[6] {
+        0 => "0".to_string(),
+        1 => "1 (current)".to_string(),
+        v => format!("{} <unknown>", v),
+    };
+    field(&mut out, "Version:", version);
+    let osabi = match options.gnu {
+        true => gnu_osabi(elf.osabi, elf.machine),
+        false => elf.osabi.to_string(),
+    };
+    field(&mut out, "OS/ABI:", osabi);
+    field(&mut out, "ABI Version:", elf.abi_version.to_string());
+    field(&mut out, "Type:", file_type(elf));
+    let machine = match options.gnu {
+        true => gnu_machine(elf.machine),
+        false => elf.machine.to_string(),
+    };
+    field(&mut out, "Machine:", machine);
+    field(&mut out, "Version:", format!("{:#x}", elf.version));
+    field(
+        &mut out,
+        "Entry point address:",
+        format!("{:#x}", elf.entry),
+    );
+    field(
+        &mut out,
+        "Start of program headers:",
+        format!("{} (bytes into file)", raw.program_header_offset),
+    );
+    field(
+        &mut out,
+        "Start of section headers:",
+        format!("{} (bytes into file)", raw.section_header_offset),
+    );
+    field(&mut out, "Flags:", format!("{:#x}", elf.flags));
+    field(
+        &mut out,
+        "Size of this header:",
+        format!("{} (bytes)", raw.file_header_size),
+    );
+    field(
+        &mut out,
+        "Size of program headers:",
+        format!("{} (bytes)", raw.program_header_size),
+    );
+    field(
+        &mut out,
+        "Number of program headers:",
+        raw.program_header_count.to_string(),
+    );
+    field(
+        &mut out,
+        "Size of section headers:",
+        format!("{} (bytes)", raw.section_header_size),
+    );
+    field(
+        &mut out,
+        "Number of section headers:",
+        raw.section_header_count.to_string(),
+    );
+    field(
+        &mut out,
+        "Section header string table index:",
+        raw.string_section_index.to_string(),
+    );
+    out
+}
+
+/// Format the program headers, like `readelf -l`.
+///
+/// The output fits in 80 columns, so the entries of a 64-bit ELF file are
+/// wrapped over two lines. Segments that can't be read are skipped.
+pub fn format_program_headers(elf: &ReadElf<'_>) -> String {
+    format_program_headers_with(elf, &Options::default())
+}
+
+/// Format the program headers on one line each, like `readelf -l -W`.
+///
+/// The output of a 32-bit ELF file is the same as [format_program_headers()].
+pub fn format_program_headers_wide(elf: &ReadElf<'_>) -> String {
+    let options = Options {
+        wide: true,
+        ..Default::default()
+    };
+    format_program_headers_with(elf, &options)
+}
+
+/// Format the program headers, like `readelf -l`, with the [Options] given.
+pub fn format_program_headers_with(elf: &ReadElf<'_>, options: &Options) -> String {
+    let raw = elf.raw_header();
+    let mut out = String::new();
+
+    if raw.program_header_count == 0 {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "There are no program headers in this file.");
+        return out;
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Elf file type is {}", file_type(elf));
+    let _ = writeln!(out, "Entry point {:#x}", elf.entry);
+    let _ = writeln!(
+        out,
+        "There are {} program headers, starting at offset {}",
+        raw.program_header_count, raw.program_header_offset
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", options.paint("Program Headers:", BOLD));
+    match elf.class {
+        Class::Elf32 => {
+            let _ = writeln!(
+                out,
+                "  Type           Offset   VirtAddr   PhysAddr   FileSiz MemSiz  Flg Align"
+            );
+            for ph in elf.program_headers() {
+                let _ = writeln!(
+                    out,
+                    "  {:<14} 0x{:06x} 0x{:08x} 0x{:08x} 0x{:05x} 0x{:05x} {} {}",
+                    machine_segment_type(ph.segment_type, elf.machine),
+                    ph.file_offset,
+                    ph.virtual_address,
+                    ph.physical_address,
+                    ph.file_size,
+                    ph.memory_size,
+                    options.flags(ph.flags),
+                    options.alignment(&ph)
+                );
+            }
+        }
+        Class::Elf64 if options.wide => {
+            let _ = writeln!(
+                out,
+                "  Type           Offset   VirtAddr           PhysAddr           FileSiz  MemSiz   Flg Align"
+            );
+            for ph in elf.program_headers() {
+                let _ = writeln!(
+                    out,
+                    "  {:<14} 0x{:06x} 0x{:016x} 0x{:016x} 0x{:06x} 0x{:06x} {} {}",
+                    machine_segment_type(ph.segment_type, elf.machine),
+                    ph.file_offset,
+                    ph.virtual_address,
+                    ph.physical_address,
+                    ph.file_size,
+                    ph.memory_size,
+                    options.flags(ph.flags),
+                    options.alignment(&ph)
+                );
+            }
+        }
+        Class::Elf64 => {
+            let _ = writeln!(
+                out,
+                "  Type           Offset             VirtAddr           PhysAddr"
+            );
+            let _ = writeln!(
+                out,
+                "                 FileSiz            MemSiz              Flags  Align"
+            );
+            for ph in elf.program_headers() {
+                let _ = writeln!(
+                    out,
+                    "  {:<14} 0x{:016x} 0x{:016x} 0x{:016x}",
+                    machine_segment_type(ph.segment_type, elf.machine),
+                    ph.file_offset,
+                    ph.virtual_address,
+                    ph.physical_address
+                );
+                let _ = writeln!(
+                    out,
+                    "                 0x{:016x} 0x{:016x}  {}    {}",
+                    ph.file_size,
+                    ph.memory_size,
+                    options.flags(ph.flags),
+                    options.alignment(&ph)
+                );
+            }
+        }
+    }
+    out
+}
+
+/// Format a range of bytes of the ELF file as a hex dump, like `readelf -x`.
+///
+/// Each line has the offset in the file, up to 16 bytes in hexadecimal in
+/// groups of four, and the bytes as ASCII, where non-printable characters are
+/// shown as `.`. The dump stops early at the end of the file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::{display, ReadElf};
+///
+/// let elf = ReadElf::open("/bin/sh").unwrap();
+/// print!("{}", display::format_hex_dump(&elf, 0, 64));
+/// ```
+pub fn format_hex_dump(elf: &ReadElf<'_>, offset: u64, length: u64) -> String {
+    let mut out = String::new();
+    let end = offset.saturating_add(length);
+
+    let mut line = offset;
+    while line < end {
+        let mut bytes = Vec::with_capacity(16);
+        for o in line..end.min(line.saturating_add(16)) {
+            match elf.byte(o) {
+                Some(b) => bytes.push(b),
+                None => break,
+            }
+        }
+        if bytes.is_empty() {
+            break;
+        }
+
+        let _ = write!(out, "  0x{:08x} ", line);
+        for i in 0..16 {
+            match bytes.get(i) {
+                Some(b) => {
+                    let _ = write!(out, "{:02x}", b);
+                }
+                None => out.push_str("  "),
+            }
+            if i % 4 == 3 {
+                out.push(' ');
+            }
+        }
+        for b in &bytes {
+            out.push(if b.is_ascii_graphic() || *b == b' ' {
+                *b as char
+            } else {
+                '.'
+            });
+        }
+        out.push('\n');
+
+        if bytes.len() < 16 {
+            break;
+        }
+        line += 16;
+    }
+    out
+}
+
+/// Format the differences between two ELF files, as given by [crate::diff()].
+///
+/// Each changed field of the ELF header is a line with the old and the new
+/// value. Segments are prefixed with `+` if they were added, `-` if they were
+/// removed, and `~` if they were changed, followed by the index of the segment
+/// in the program header table. If a program header table can't be read
+/// completely, the reason is printed last. Nothing is printed if there are no
+/// differences.
+pub fn format_diff(diff: &Diff) -> String {
+    let mut out = String::new();
+
+    if !diff.header.is_empty() {
+        let _ = writeln!(out, "ELF Header:");
+        for change in &diff.header {
+            let (name, old, new) = header_change(change);
+            let _ = writeln!(out, "  {:<34} {} -> {}", name, old, new);
+        }
+    }
+
+    if !diff.segments.is_empty() {
+        let _ = writeln!(out, "Program Headers:");
+        for change in &diff.segments {
+            let _ = match change {
+                SegmentChange::Added { index, segment } => writeln!(
+                    out,
+                    "  + [{}] {} {}",
+                    index,
+                    segment_type(segment.segment_type),
+                    segment_summary(segment)
+                ),
+                SegmentChange::Removed { index, segment } => writeln!(
+                    out,
+                    "  - [{}] {} {}",
+                    index,
+                    segment_type(segment.segment_type),
+                    segment_summary(segment)
+                ),
+                SegmentChange::Resized {
+                    old_index,
+                    new_index,
+                    segment_type: t,
+                    old_size,
+                    new_size,
+                } => writeln!(
+                    out,
+                    "  ~ [{} -> {}] {} FileSiz {:#x} -> {:#x}, MemSiz {:#x} -> {:#x}",
+                    old_index,
+                    new_index,
+                    segment_type(*t),
+                    old_size.0,
+                    new_size.0,
+                    old_size.1,
+                    new_size.1
+                ),
+                SegmentChange::Moved {
+                    old_index,
+                    new_index,
+                    segment_type: t,
+                    old_location,
+                    new_location,
+                } => writeln!(
+                    out,
+                    "  ~ [{} -> {}] {} Offset {:#x} -> {:#x}, VirtAddr {:#x} -> {:#x}",
+                    old_index,
+                    new_index,
+                    segment_type(*t),
+                    old_location.0,
+                    new_location.0,
+                    old_location.1,
+                    new_location.1
+                ),
+                SegmentChange::PhysicalAddress {
+                    old_index,
+                    new_index,
+                    segment_type: t,
+                    old_address,
+                    new_address,
+                } => writeln!(
+                    out,
+                    "  ~ [{} -> {}] {} PhysAddr {:#x} -> {:#x}",
+                    old_index,
+                    new_index,
+                    segment_type(*t),
+                    old_address,
+                    new_address
+                ),
+                SegmentChange::Flags {
+                    old_index,
+                    new_index,
+                    segment_type: t,
+                    old_flags,
+                    new_flags,
+                } => writeln!(
+                    out,
+                    "  ~ [{} -> {}] {} Flg {} -> {}",
+                    old_index,
+                    new_index,
+                    segment_type(*t),
+                    segment_flags(*old_flags).trim_end(),
+                    segment_flags(*new_flags).trim_end()
+                ),
+                SegmentChange::Alignment {
+                    old_index,
+                    new_index,
+                    segment_type: t,
+                    old_alignment,
+                    new_alignment,
+                } => writeln!(
+                    out,
+                    "  ~ [{} -> {}] {} Align {:#x} -> {:#x}",
+                    old_index,
+                    new_index,
+                    segment_type(*t),
+                    old_alignment,
+                    new_alignment
+                ),
+            };
+        }
+    }
+
+    for (file, error) in [
+        ("first", &diff.old_segment_error),
+        ("second", &diff.new_segment_error),
+    ] {
+        if let Some(e) = error {
+            let _ = writeln!(out, "Program headers of the {} file: {}", file, e);
+        }
+    }
+    out
+}
+
+fn segment_summary(ph: &ProgramHeader) -> String {
+    format!(
+        "Offset {:#x}, VirtAddr {:#x}, FileSiz {:#x}, MemSiz {:#x}, Flg {}",
+        ph.file_offset,
+        ph.virtual_address,
+        ph.file_size,
+        ph.memory_size,
+        segment_flags(ph.flags).trim_end()
+    )
+}
+
+fn header_change(change: &HeaderChange) -> (&'static str, String, String) {
+    match change {
+        HeaderChange::Class(a, b) => ("Class:", class(*a), class(*b)),
+        HeaderChange::Data(a, b) => ("Data:", data(*a), data(*b)),
+        HeaderChange::Version(a, b) => ("Version:", a.to_string(), b.to_string()),
+        HeaderChange::OsAbi(a, b) => ("OS/ABI:", a.to_string(), b.to_string()),
+        HeaderChange::AbiVersion(a, b) => ("ABI Version:", a.to_string(), b.to_string()),
+        HeaderChange::ExecutableType(a, b) => ("Type:", executable_type(*a), executable_type(*b)),
+        HeaderChange::Machine(a, b) => ("Machine:", a.to_string(), b.to_string()),
+        HeaderChange::Entry(a, b) => (
+            "Entry point address:",
+            format!("{:#x}", a),
+            format!("{:#x}", b),
+        ),
+        HeaderChange::Flags(a, b) => ("Flags:", format!("{:#x}", a), format!("{:#x}", b)),
+        HeaderChange::FileHeaderSize(a, b) => {
+            ("Size of this header:", a.to_string(), b.to_string())
+        }
+        HeaderChange::ProgramHeaderOffset(a, b) => {
+            ("Start of program headers:", a.to_string(), b.to_string())
+        }
+        HeaderChange::ProgramHeaderSize(a, b) => {
+            ("Size of program headers:", a.to_string(), b.to_string())
+        }
+        HeaderChange::ProgramHeaderCount(a, b) => {
+            ("Number of program headers:", a.to_string(), b.to_string())
+        }
+        HeaderChange::SectionHeaderOffset(a, b) => {
+            ("Start of section headers:", a.to_string(), b.to_string())
+        }
+        HeaderChange::SectionHeaderSize(a, b) => {
+            ("Size of section headers:", a.to_string(), b.to_string())
+        }
+        HeaderChange::SectionHeaderCount(a, b) => {
+            ("Number of section headers:", a.to_string(), b.to_string())
+        }
+        HeaderChange::StringSectionIndex(a, b) => (
+            "Section header string table index:",
+            a.to_string(),
+            b.to_string(),
+        ),
+    }
+}
+
+/// Format the printable strings in a range of bytes of the ELF file, like
+/// `readelf -p`.
+///
+/// A string is a sequence of at least `min_length` printable ASCII characters
+/// (including space and tab). Each line has the offset of the string in the
+/// file and the string. The scan stops early at the end of the file.
+pub fn format_strings(elf: &ReadElf<'_>, offset: u64, length: u64, min_length: usize) -> String {
+    let mut out = String::new();
+    let end = offset.saturating_add(length);
+
+    let mut start = offset;
+    let mut current = String::new();
+    let mut position = offset;
+    loop {
+        let b = if position < end {
+            elf.byte(position)
+        } else {
+            None
+        };
+        match b {
+            Some(b) if b.is_ascii_graphic() || b == b' ' || b == b'\t' => {
+                if current.is_empty() {
+                    start = position;
+                }
+                current.push(b as char);
+            }
+            _ => {
+                if current.len() >= min_length.max(1) {
+                    let _ = writeln!(out, "  [{:>6x}]  {}", start, current);
+                }
+                current.clear();
+                if b.is_none() {
+                    break;
+                }
+            }
+        }
+        position += 1;
+    }
+    out
+}
+
+/// Format a summary of the ELF file on one line, similar to `file(1)`.
+///
+/// The line has the name given, the class, the endianness, the machine, the
+/// type of the file and the program interpreter if there is one.
+pub fn format_summary(elf: &ReadElf<'_>, name: &str, options: &Options) -> String {
+    let machine = match options.gnu {
+        true => gnu_machine(elf.machine),
+        false => elf.machine.to_string(),
+    };
+    let endian = match elf.data {
+        Endian::Little => "little endian",
+        Endian::Big => "big endian",
+    };
+
+    let mut out = format!(
+        "{}: {}, {}, {}, {}",
+        name,
+        class(elf.class),
+        endian,
+        machine,
+        file_type(elf)
+    );
+    if let Some(interpreter) = elf.interpreter() {
+        let _ = write!(out, ", interpreter {}", interpreter);
+    }
+    out
+}
+
+/// The reason why a template can't be formatted by [format_template()].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateError {
+    /// The field with the name given is not known.
+    UnknownField(String),
+
+    /// The field with the name given has no closing `}`.
+    Unterminated(String),
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnknownField(field) => write!(f, "unknown field %{{{}}}", field),
+            TemplateError::Unterminated(field) => write!(f, "field %{{{} is not terminated", field),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Format the fields of the ELF file given by a template, like `printf`.
+///
+/// The fields are written as `%{name}`, where `name` is one of `file` (the
+/// name given), `class`, `data`, `version`, `osabi`, `abi_version`, `type`,
+/// `machine`, `entry`, `flags`, `phoff`, `phnum`, `shoff`, `shnum` and
+/// `interpreter`. The addresses `entry`, `phoff` and `shoff`, and `flags` are
+/// written in hexadecimal. The escape sequences `\n`, `\t`, `\\` and `%%` are
+/// supported.
+///
+/// # Errors
+///
+/// Returns a [TemplateError] with the name of the first field in the template
+/// that is unknown, or that is not terminated.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::{display, ReadElf};
+///
+/// let elf = ReadElf::open("/bin/sh").unwrap();
+/// let line = display::format_template(&elf, "/bin/sh", "%{machine} %{entry}\\n");
+/// print!("{}", line.unwrap());
+/// ```
+pub fn format_template(
+    elf: &ReadElf<'_>,
+    name: &str,
+    template: &str,
+) -> Result<String, TemplateError> {
+    let raw = elf.raw_header();
+    let mut out = String::new();
+
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(c) => out.push(c),
+                None => out.push('\\'),
+            },
+            '%' => match chars.next() {
+                Some('%') => out.push('%'),
+                Some('{') => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => return Err(TemplateError::Unterminated(field)),
+                        }
+                    }
+                    let _ = match field.as_str() {
+                        "file" => write!(out, "{}", name),
+                        "class" => write!(out, "{}", class(elf.class)),
+                        "data" => write!(out, "{}", data(elf.data)),
+                        "version" => write!(out, "{}", elf.version),
+                        "osabi" => write!(out, "{}", elf.osabi),
+                        "abi_version" => write!(out, "{}", elf.abi_version),
+                        "type" => write!(out, "{}", file_type(elf)),
+                        "machine" => write!(out, "{}", elf.machine),
+                        "entry" => write!(out, "{:#x}", elf.entry),
+                        "flags" => write!(out, "{:#x}", elf.flags),
+                        "phoff" => write!(out, "{:#x}", raw.program_header_offset),
+                        "phnum" => write!(out, "{}", raw.program_header_count),
+                        "shoff" => write!(out, "{:#x}", raw.section_header_offset),
+                        "shnum" => write!(out, "{}", raw.section_header_count),
+                        "interpreter" => write!(out, "{}", elf.interpreter().unwrap_or_default()),
+                        _ => return Err(TemplateError::UnknownField(field)),
+                    };
+                }
+                Some(c) => {
+                    out.push('%');
+                    out.push(c);
+                }
+                None => out.push('%'),
+            },
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+/// The heading of the output of [format_size()], like `size(1)`.
+pub const SIZE_HEADER: &str = "   text\t   data\t    bss\t    dec\t    hex\tfilename";
+
+/// Format the sizes of a file as a line of `size(1)` in the Berkeley format.
+///
+/// The columns are text, data, bss, their total in decimal and hexadecimal,
+/// and the name given. Print [SIZE_HEADER] before the first line.
+pub fn format_size(report: &SizeReport, name: &str) -> String {
+    format!(
+        "{:>7}\t{:>7}\t{:>7}\t{:>7}\t{:>7x}\t{}",
+        report.text,
+        report.data,
+        report.bss,
+        report.total(),
+        report.total(),
+        name
+    )
+}
+
+/// Format the flags of a segment as `readelf` does, e.g. `R E`.
+///
+/// Flags other than `R`, `W` and `X` are appended in hexadecimal.
+pub fn segment_flags(flags: SegmentFlags) -> String {
+    let f = flags.flags();
+    let mut result = String::with_capacity(3);
+    result.push(if flags.is_readable() { 'R' } else { ' ' });
+    result.push(if flags.is_writable() { 'W' } else { ' ' });
+    result.push(if flags.is_executable() { 'E' } else { ' ' });
+
+    let other = f & !(SegmentFlags::R | SegmentFlags::W | SegmentFlags::X);
+    if other != 0 {
+        let _ = write!(result, " {:#x}", other);
+    }
+    result
+}
+
+/// Format the segment type as `readelf` does, e.g. `LOAD`.
+///
+/// A name registered with [SegmentType::register_name()] for all files is
+/// preferred. The program headers of a file also use the names registered for
+/// its machine.
+pub fn segment_type(segment_type: SegmentType) -> String {
+    if let Some(name) = segment_type.registered_name(None) {
+        return name;
+    }
+
+    let v = u32::from(segment_type);
+    match v {
+        0 => "NULL".to_string(),
+        1 => "LOAD".to_string(),
+        2 => "DYNAMIC".to_string(),
+        3 => "INTERP".to_string(),
+        4 => "NOTE".to_string(),
+        5 => "SHLIB".to_string(),
+        6 => "PHDR".to_string(),
+        7 => "TLS".to_string(),
+        0x6474E550 => "GNU_EH_FRAME".to_string(),
+        0x6474E551 => "GNU_STACK".to_string(),
+        0x6474E552 => "GNU_RELRO".to_string(),
+        0x6474E553 => "GNU_PROPERTY".to_string(),
+        0x60000000..=0x6FFFFFFF => format!("LOOS+0x{:x}", v - 0x60000000),
+        0x70000000..=0x7FFFFFFF => format!("LOPROC+0x{:x}", v - 0x70000000),
+        _ => format!("<unknown>: {:x}", v),
+    }
+}
+
+/// Get the name of the segment type, preferring a name registered for the
+/// machine with [SegmentType::register_name()].
+fn machine_segment_type(t: SegmentType, machine: Machine) -> String {
+    match t.registered_name(Some(machine)) {
+        Some(name) => name,
+        None => segment_type(t),
+    }
+}
+
+fn field(out: &mut String, name: &str, value: String) {
+    let _ = writeln!(out, "  {:<34} {}", name, value);
+}
+
+fn class(class: Class) -> String {
+    match class {
+        Class::Elf32 => "ELF32".to_string(),
+        Class::Elf64 => "ELF64".to_string(),
+    }
+}
+
+fn data(data: Endian) -> String {
+    match data {
+        Endian::Little => "2's complement, little endian".to_string(),
+        Endian::Big => "2's complement, big endian".to_string(),
+    }
+}
+
+fn file_type(elf: &ReadElf<'_>) -> String {
+    if elf.exec_type == ExecutableType::Dynamic && elf.is_pie() {
+        "DYN (Position-Independent Executable file)".to_string()
+    } else {
+        executable_type(elf.exec_type)
+    }
+}
+
+fn executable_type(exec_type: ExecutableType) -> String {
+    match exec_type {
+        ExecutableType::None => "NONE (None)".to_string(),
+        ExecutableType::Relocatable => "REL (Relocatable file)".to_string(),
+        ExecutableType::Executable => "EXEC (Executable file)".to_string(),
+        ExecutableType::Dynamic => "DYN (Shared object file)".to_string(),
+        ExecutableType::Core => "CORE (Core file)".to_string(),
+        ExecutableType::Unknown(v @ 0xFE00..=0xFEFF) => format!("OS Specific: ({:x})", v),
+        ExecutableType::Unknown(v @ 0xFF00..=0xFFFF) => format!("Processor Specific: ({:x})", v),
+        ExecutableType::Unknown(v) => format!("<unknown>: {:x}", v),
+    }
+}
+
+fn gnu_osabi(osabi: OsAbi, machine: Machine) -> String {
+    let name = match osabi.os_abi() {
+        OsAbi::SYSV => "UNIX - System V",
+        OsAbi::HPUX => "UNIX - HP-UX",
+        OsAbi::NETBSD => "UNIX - NetBSD",
+        OsAbi::LINUX => "UNIX - GNU",
+        OsAbi::SOLARIS => "UNIX - Solaris",
+        OsAbi::AIX => "UNIX - AIX",
+        OsAbi::IRIX => "UNIX - IRIX",
+        OsAbi::FREEBSD => "UNIX - FreeBSD",
+        OsAbi::TRU64 => "UNIX - TRU64",
+        OsAbi::MODESTO => "Novell - Modesto",
+        OsAbi::OPENBSD => "UNIX - OpenBSD",
+        OsAbi::OPENVMS => "VMS - OpenVMS",
+        OsAbi::NSK => "HP - Non-Stop Kernel",
+        OsAbi::AROS => "AROS",
+        OsAbi::FENIXOS => "FenixOS",
+        OsAbi::CLOUDABI => "Nuxi CloudABI",
+        OsAbi::OPENVOS => "Stratus Technologies OpenVOS",
+        OsAbi::ARM if machine.machine() == Machine::ARM => "ARM",
+        OsAbi::STANDALONE => "Standalone App",
+        v => return format!("<unknown: {:x}>", v),
+    };
+    name.to_string()
+}
+
+fn gnu_machine(machine: Machine) -> String {
+    let name = match machine.machine() {
+        Machine::NONE => "None",
+        Machine::SPARC => "Sparc",
+        Machine::INTEL_386 => "Intel 80386",
+        Machine::MOTOROLA_68K => "MC68000",
+        Machine::IAMCU => "Intel MCU",
+        Machine::INTEL_860 => "Intel 80860",
+        Machine::MIPS => "MIPS R3000",
+        Machine::MIPS_RS3_LE => "MIPS R4000 big-endian",
+        Machine::PARISC => "HPPA",
+        Machine::SPARC32PLUS => "Sparc v8+",
+        Machine::PPC => "PowerPC",
+        Machine::PPC64 => "PowerPC64",
+        Machine::S390 => "IBM S/390",
+        Machine::ARM => "ARM",
+        Machine::SH => "Renesas / SuperH SH",
+        Machine::SPARCV9 => "Sparc v9",
+        Machine::IA_64 => "Intel IA-64",
+        Machine::X86_64 => "Advanced Micro Devices X86-64",
+        Machine::AVR => "Atmel AVR 8-bit microcontroller",
+        Machine::XTENSA => "Tensilica Xtensa Processor",
+        Machine::AARCH64 => "AArch64",
+        Machine::RISCV => "RISC-V",
+        Machine::BPF => "Linux BPF",
+        Machine::LOONGARCH => "LoongArch",
+        _ => return machine.to_string(),
+    };
+    name.to_string()
+}
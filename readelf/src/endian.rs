@@ -22,7 +22,7 @@ use std::fmt;
 /// let v: u8 = e.into();
 /// println!("Little Endian has value {}", v);
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Endian {
     /// Little Endian format, where the first byte is the lowest order byte.
     Little = 1,
@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// The reason why an element of the ELF file can't be read.
+///
+/// The ELF file is read lazily, so a file that could be opened may still have
+/// corrupted or truncated contents, that is only known when the element is
+/// read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The size of an entry in a header table, as given by the ELF header, is
+    /// smaller than the structure that needs to be read.
+    ///
+    /// The value is the entry size from the ELF header, e.g. `e_phentsize`.
+    EntrySize(u16),
+
+    /// The offset of the entry at the index given overflows a 64-bit value.
+    Overflow(usize),
+
+    /// The entry at the index given is not completely inside the file.
+    Truncated(usize),
+
+    /// The number of program headers given exceeds
+    /// [ReadOptions::max_program_headers](crate::ReadOptions::max_program_headers).
+    ProgramHeaderLimit(u32),
+
+    /// The number of section headers given exceeds
+    /// [ReadOptions::max_section_headers](crate::ReadOptions::max_section_headers).
+    SectionHeaderLimit(u64),
+
+    /// The contents are not an ELF file that can be read.
+    ///
+    /// The magic, class or endianness is not valid, the version is not
+    /// supported, or the ELF header is truncated.
+    NotElf,
+
+    /// The file can't be read, with the kind of the I/O error.
+    Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::EntrySize(v) => write!(f, "Entry size {} is too small", v),
+            ParseError::Overflow(i) => write!(f, "Offset of entry {} overflows", i),
+            ParseError::Truncated(i) => write!(f, "Entry {} is truncated", i),
+            ParseError::ProgramHeaderLimit(n) => {
+                write!(f, "Number of program headers {} exceeds the limit", n)
+            }
+            ParseError::SectionHeaderLimit(n) => {
+                write!(f, "Number of section headers {} exceeds the limit", n)
+            }
+            ParseError::NotElf => write!(f, "Not an ELF file"),
+            ParseError::Io(kind) => write!(f, "Can't read the file: {}", kind),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn parse_error_to_string() {
+        assert_eq!(
+            ParseError::EntrySize(31).to_string(),
+            "Entry size 31 is too small"
+        );
+        assert_eq!(
+            ParseError::Overflow(1).to_string(),
+            "Offset of entry 1 overflows"
+        );
+        assert_eq!(ParseError::Truncated(2).to_string(), "Entry 2 is truncated");
+        assert_eq!(
+            ParseError::ProgramHeaderLimit(100).to_string(),
+            "Number of program headers 100 exceeds the limit"
+        );
+        assert_eq!(
+            ParseError::SectionHeaderLimit(70000).to_string(),
+            "Number of section headers 70000 exceeds the limit"
+        );
+        assert_eq!(ParseError::NotElf.to_string(), "Not an ELF file");
+        assert_eq!(
+            ParseError::Io(std::io::ErrorKind::NotFound).to_string(),
+            "Can't read the file: entity not found"
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// The executable type of the ELF file.
 ///
@@ -51,7 +53,10 @@ use std::fmt;
 ///   }
 /// }
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// Two values are compared, hashed and ordered by their value, so that
+/// `ExecutableType::Unknown(2)` equals `ExecutableType::Executable`.
+#[derive(Clone, Copy, Debug)]
 #[repr(u16)]
 pub enum ExecutableType {
     /// Unknown executable type.
@@ -137,6 +142,34 @@ impl From<ExecutableType> for u16 {
     }
 }
 
+// The traits use the value, so that an unknown variant with the value of a
+// known variant is the same as the known variant.
+impl PartialEq for ExecutableType {
+    fn eq(&self, other: &Self) -> bool {
+        u16::from(*self) == u16::from(*other)
+    }
+}
+
+impl Eq for ExecutableType {}
+
+impl Hash for ExecutableType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u16::from(*self).hash(state);
+    }
+}
+
+impl PartialOrd for ExecutableType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExecutableType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u16::from(*self).cmp(&u16::from(*other))
+    }
+}
+
 impl fmt::Display for ExecutableType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -153,6 +186,7 @@ impl fmt::Display for ExecutableType {
 #[cfg(test)]
 mod tests {
     use super::ExecutableType;
+    use std::collections::HashSet;
 
     #[test]
     fn from_value() {
@@ -192,4 +226,17 @@ mod tests {
         assert_eq!(ExecutableType::Unknown(0xFF).to_string(), "Type 0x00FF");
         assert_eq!(ExecutableType::Unknown(0xFFFF).to_string(), "Type 0xFFFF");
     }
+
+    #[test]
+    fn compare_by_value() {
+        assert_eq!(ExecutableType::Unknown(2), ExecutableType::Executable);
+        assert_ne!(ExecutableType::Unknown(5), ExecutableType::Executable);
+        assert!(ExecutableType::Unknown(1) < ExecutableType::Executable);
+
+        let types: HashSet<ExecutableType> =
+            [ExecutableType::Unknown(2), ExecutableType::Executable]
+                .into_iter()
+                .collect();
+        assert_eq!(types.len(), 1);
+    }
 }
@@ -0,0 +1,255 @@
+use crate::Endian;
+use std::path::{Path, PathBuf};
+
+/// The magic of the old format, used until glibc 2.32.
+const CACHE_MAGIC_OLD: &[u8] = b"ld.so-1.7.0";
+
+/// The magic and version of the new format.
+const CACHE_MAGIC_NEW: &[u8] = b"glibc-ld.so.cache1.1";
+
+/// The size of the header and an entry of the old format.
+const HEADER_SIZE_OLD: usize = 16;
+const ENTRY_SIZE_OLD: usize = 12;
+
+/// The size of the header and an entry of the new format.
+const HEADER_SIZE_NEW: usize = 48;
+const ENTRY_SIZE_NEW: usize = 24;
+
+/// A shared object in the cache of the dynamic loader.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LdSoCacheEntry {
+    /// The name of the shared object, e.g. `libc.so.6`.
+    pub name: String,
+
+    /// The path of the shared object.
+    pub path: PathBuf,
+
+    /// The flags of the entry, with the type of the library in the low byte
+    /// (e.g. `3` for glibc) and the architecture in the next byte (e.g.
+    /// `0x03` for x86-64).
+    pub flags: i32,
+}
+
+/// The cache of the dynamic loader, `/etc/ld.so.cache`, written by
+/// `ldconfig`.
+///
+/// Both the old format (`ld.so-1.7.0`), the new format
+/// (`glibc-ld.so.cache1.1`), and the old format followed by the new format are
+/// read. The entries of the new format are preferred.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::LdSoCache;
+///
+/// let cache = LdSoCache::open("/etc/ld.so.cache").unwrap();
+/// for path in cache.find("libc.so.6") {
+///     println!("{}", path.display());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LdSoCache {
+    /// The entries of the cache, in the order of the file.
+    pub entries: Vec<LdSoCacheEntry>,
+}
+
+impl LdSoCache {
+    /// Read the cache from a file.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the file can't be read, or is not a valid
+    /// cache.
+    pub fn open<P: AsRef<Path>>(path: P) -> Option<LdSoCache> {
+        LdSoCache::parse(&std::fs::read(path).ok()?)
+    }
+
+    /// Interpret the contents of the cache.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the contents are not a valid cache.
+    pub fn parse(cache: &[u8]) -> Option<LdSoCache> {
+        if cache.starts_with(CACHE_MAGIC_NEW) {
+            return LdSoCache::parse_new(cache);
+        }
+        if !cache.starts_with(CACHE_MAGIC_OLD) {
+            return None;
+        }
+
+        // The old format has no marker for the endianness, so the number of
+        // entries must fit in the file.
+        let endian = [Endian::Little, Endian::Big].into_iter().find(|e| {
+            read_u32(cache, 12, *e)
+                .and_then(|n| (n as usize).checked_mul(ENTRY_SIZE_OLD))
+                .and_then(|n| n.checked_add(HEADER_SIZE_OLD))
+                .is_some_and(|n| n <= cache.len())
+        })?;
+        let count = read_u32(cache, 12, endian)? as usize;
+        let strings = HEADER_SIZE_OLD + count * ENTRY_SIZE_OLD;
+
+        // A cache in the new format may follow, aligned to 8 bytes.
+        let new = (strings + 7) & !7;
+        if let Some(cache) = cache.get(new..) {
+            if cache.starts_with(CACHE_MAGIC_NEW) {
+                return LdSoCache::parse_new(cache);
+            }
+        }
+
+        let entries = (0..count)
+            .map(|i| {
+                let entry = HEADER_SIZE_OLD + i * ENTRY_SIZE_OLD;
+                read_entry(cache, entry, strings, endian)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(LdSoCache { entries })
+    }
+
+    fn parse_new(cache: &[u8]) -> Option<LdSoCache> {
+        let endian = match cache.get(28)? {
+            2 => Endian::Little,
+            3 => Endian::Big,
+            _ if cfg!(target_endian = "big") => Endian::Big,
+            _ => Endian::Little,
+        };
+        let count = read_u32(cache, 20, endian)? as usize;
+
+        // The strings are relative to the start of the new format.
+        let entries = (0..count)
+            .map(|i| {
+                let entry = HEADER_SIZE_NEW.checked_add(i.checked_mul(ENTRY_SIZE_NEW)?)?;
+                read_entry(cache, entry, 0, endian)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(LdSoCache { entries })
+    }
+
+    /// Get the paths of the shared objects with the name, in the order of the
+    /// cache.
+    ///
+    /// There may be more than one path for different architectures, e.g. for
+    /// 32-bit and 64-bit libraries.
+    pub fn find<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Path> + 'a {
+        self.entries
+            .iter()
+            .filter(move |e| e.name == name)
+            .map(|e| e.path.as_path())
+    }
+}
+
+/// Read an entry, with the flags, the offset of the name, and the offset of
+/// the path, where the offsets are relative to `strings`.
+fn read_entry(
+    cache: &[u8],
+    entry: usize,
+    strings: usize,
+    endian: Endian,
+) -> Option<LdSoCacheEntry> {
+    let flags = read_u32(cache, entry, endian)? as i32;
+    let name = read_string(
+        cache,
+        strings.checked_add(read_u32(cache, entry + 4, endian)? as usize)?,
+    )?;
+    let path = read_string(
+        cache,
+        strings.checked_add(read_u32(cache, entry + 8, endian)? as usize)?,
+    )?;
+    Some(LdSoCacheEntry {
+        name,
+        path: PathBuf::from(path),
+        flags,
+    })
+}
+
+fn read_u32(cache: &[u8], offset: usize, endian: Endian) -> Option<u32> {
+    let bytes: [u8; 4] = cache.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+    match endian {
+        Endian::Little => Some(u32::from_le_bytes(bytes)),
+        Endian::Big => Some(u32::from_be_bytes(bytes)),
+    }
+}
+
+fn read_string(cache: &[u8], offset: usize) -> Option<String> {
+    let bytes = cache.get(offset..)?;
+    let end = bytes.iter().position(|b| *b == 0)?;
+    Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LdSoCache;
+    use std::path::Path;
+
+    /// Build a cache in the old format. The new format, if given, follows the
+    /// entries, as written by `ldconfig -c compat`.
+    fn old_cache(new: Option<&[u8]>) -> Vec<u8> {
+        let mut cache = b"ld.so-1.7.0\0".to_vec();
+        cache.extend_from_slice(&1_u32.to_le_bytes());
+        cache.extend_from_slice(&1_u32.to_le_bytes());
+        cache.extend_from_slice(&0_u32.to_le_bytes());
+        cache.extend_from_slice(&8_u32.to_le_bytes());
+        if let Some(new) = new {
+            cache.resize((cache.len() + 7) & !7, 0);
+            cache.extend_from_slice(new);
+        }
+        cache.extend_from_slice(b"libm.so\0/lib/libm.so\0");
+        cache
+    }
+
+    fn new_cache() -> Vec<u8> {
+        let mut cache = b"glibc-ld.so.cache1.1".to_vec();
+        cache.extend_from_slice(&2_u32.to_le_bytes());
+        cache.extend_from_slice(&0_u32.to_le_bytes());
+        cache.extend_from_slice(&[2, 0, 0, 0]);
+        cache.resize(48, 0);
+        for (flags, name, path) in [(0x0303, 96, 106), (0x0003, 96, 123)] {
+            cache.extend_from_slice(&(flags as u32).to_le_bytes());
+            cache.extend_from_slice(&(name as u32).to_le_bytes());
+            cache.extend_from_slice(&(path as u32).to_le_bytes());
+            cache.extend_from_slice(&[0; 12]);
+        }
+        cache.extend_from_slice(b"libc.so.6\0/lib64/libc.so.6\0/lib32/libc.so.6\0");
+        cache
+    }
+
+    #[test]
+    fn parse_old() {
+        let cache = LdSoCache::parse(&old_cache(None)).unwrap();
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.entries[0].name, "libm.so");
+        assert_eq!(cache.entries[0].flags, 1);
+        assert_eq!(
+            cache.find("libm.so").collect::<Vec<_>>(),
+            vec![Path::new("/lib/libm.so")]
+        );
+    }
+
+    #[test]
+    fn parse_new() {
+        let cache = LdSoCache::parse(&new_cache()).unwrap();
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.entries[0].flags, 0x0303);
+        assert_eq!(
+            cache.find("libc.so.6").collect::<Vec<_>>(),
+            vec![Path::new("/lib64/libc.so.6"), Path::new("/lib32/libc.so.6")]
+        );
+        assert_eq!(cache.find("libm.so").count(), 0);
+    }
+
+    #[test]
+    fn parse_compat() {
+        let cache = LdSoCache::parse(&old_cache(Some(&new_cache()))).unwrap();
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.find("libm.so").count(), 0);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(LdSoCache::parse(b""), None);
+        assert_eq!(LdSoCache::parse(b"ld.so-1.7.0\0\xff\xff\xff\x7f"), None);
+
+        let mut cache = new_cache();
+        cache.truncate(100);
+        assert_eq!(LdSoCache::parse(&cache), None);
+    }
+}
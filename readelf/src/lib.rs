@@ -16,6 +16,9 @@
 #![deny(non_ascii_idents)]
 #![deny(trivial_numeric_casts)]
 
+mod error;
+pub use error::ParseError;
+
 mod endian;
 pub use endian::Endian;
 
@@ -37,7 +40,26 @@ pub use segment_type::SegmentType;
 mod segment_flags;
 pub use segment_flags::SegmentFlags;
 
+mod read_options;
+pub use read_options::ReadOptions;
+
 mod readelf;
-pub use readelf::{ProgramHeader, ProgramHeaders, ReadElf};
+pub use readelf::{
+    AndroidDynamic, AndroidIdent, ArmExceptionIndex, ArmUnwind, BinaryImage, BinaryImageOptions,
+    GoBuildInfo, GoModule, ModuleSignature, Note, PackageMetadata, PackerIndicator, PackerReport,
+    ProgramHeader, ProgramHeaders, ProgramHeadersChecked, ProgramHeadersIndexed, RawHeader,
+    ReadElf, ReproducibilityReport, SegmentSize, Segments, SizeReport,
+};
+
+mod diff;
+pub use diff::{diff, Diff, HeaderChange, SegmentChange};
+
+mod dependencies;
+pub use dependencies::{resolve_dependencies, Dependency, SearchConfig};
+
+mod ld_so_cache;
+pub use ld_so_cache::{LdSoCache, LdSoCacheEntry};
+
+pub mod display;
 
 mod binparser;
@@ -1,4 +1,9 @@
+use std::collections::BTreeMap;
 use std::fmt;
+use std::sync::{PoisonError, RwLock};
+
+/// The names of machines registered with [Machine::register_name()].
+static REGISTERED_NAMES: RwLock<BTreeMap<u16, String>> = RwLock::new(BTreeMap::new());
 
 /// The target instruction set architecture for the ELF file.
 ///
@@ -24,7 +29,7 @@ use std::fmt;
 /// let m = Machine::from(Machine::NONE);
 /// println!("{:?}", m);
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Machine {
     machine: u16,
 }
@@ -838,6 +843,91 @@ impl Machine {
         self.machine
     }
 
+    /// Register the name of a machine value that has no known name, such as
+    /// a vendor specific value.
+    ///
+    /// The name is used by [fmt::Display] instead of `Machine 0xNNNN` for all
+    /// [Machine] instances of the process. Registering a name again for the
+    /// same value replaces it.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the value is one of the constants of [Machine], or
+    /// already has a known name, which can't be changed. This includes
+    /// values of conflicting constants, that are printed as the value only.
+    ///
+    /// # Example
+    /// ```rust
+    /// use readelf::Machine;
+    ///
+    /// assert!(Machine::register_name(0x3000, "Vendor DSP"));
+    /// assert_eq!(Machine::from(0x3000).to_string(), "Vendor DSP");
+    /// ```
+    pub fn register_name(machine: u16, name: &str) -> bool {
+        if Machine::is_known(machine) {
+            return false;
+        }
+        REGISTERED_NAMES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(machine, name.to_string());
+        true
+    }
+
+    /// Remove the name of a machine value registered with
+    /// [Machine::register_name()].
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if a name was registered for the value.
+    pub fn unregister_name(machine: u16) -> bool {
+        REGISTERED_NAMES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&machine)
+            .is_some()
+    }
+
+    /// Get the largest page size that a Linux kernel for the architecture
+    /// may be configured with.
+    ///
+    /// Loadable segments must be aligned to at least this size to run on all
+    /// kernels of the architecture. For example, [Machine::AARCH64] kernels
+    /// use pages of 4KiB, 16KiB or 64KiB.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the page sizes of the architecture are not
+    /// known.
+    #[must_use]
+    pub const fn max_page_size(&self) -> Option<u64> {
+        match self.machine {
+            Machine::INTEL_386
+            | Machine::X86_64
+            | Machine::ARM
+            | Machine::RISCV
+            | Machine::S390
+            | Machine::MOTOROLA_68K => Some(0x1000),
+            Machine::ALPHA | Machine::SPARC | Machine::SPARCV9 => Some(0x2000),
+            Machine::AARCH64
+            | Machine::PPC
+            | Machine::PPC64
+            | Machine::MIPS
+            | Machine::LOONGARCH
+            | Machine::IA_64
+            | Machine::PARISC => Some(0x10000),
+            _ => None,
+        }
+    }
+
+    /// Check if the value has a built in name.
+    ///
+    /// Every constant of [Machine] has a name in [Machine::name()], so this
+    /// is also true for all the constants.
+    fn is_known(machine: u16) -> bool {
+        Machine { machine }.name().is_some()
+    }
+
     const fn name(&self) -> Option<&str> {
         match self.machine {
             Machine::NONE => Some("NONE"),
@@ -1077,14 +1167,12 @@ impl Machine {
 }
 
 impl From<u16> for Machine {
-    #[must_use]
     fn from(v: u16) -> Self {
         Machine { machine: v }
     }
 }
 
 impl From<Machine> for u16 {
-    #[must_use]
     fn from(v: Machine) -> Self {
         v.machine
     }
@@ -1096,9 +1184,17 @@ impl fmt::Display for Machine {
     /// Takes the machine value and prints the name for it. Official names are
     /// preferred over "old" or unofficial names. This means that unofficial
     /// names may change in the future. On conflicts, only the machine value is
-    /// printed.
+    /// printed. Values without a known name use the name registered with
+    /// [Machine::register_name()], if any.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.name() {
+        if let Some(v) = self.name() {
+            return write!(f, "{}", v);
+        }
+
+        let registered = REGISTERED_NAMES
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        match registered.get(&self.machine) {
             Some(v) => write!(f, "{}", v),
             None => write!(f, "Machine 0x{:0>4X}", self.machine),
         }
@@ -1508,6 +1604,25 @@ mod tests {
         assert_eq!(Machine::from(0xFEED).to_string(), "Moxie (old)");
     }
 
+    #[test]
+    fn register_name() {
+        // Use a value that no other test formats, as the names are shared by
+        // the process.
+        assert!(Machine::register_name(0x2000, "Vendor A"));
+        assert_eq!(Machine::from(0x2000).to_string(), "Vendor A");
+        assert!(Machine::register_name(0x2000, "Vendor B"));
+        assert_eq!(Machine::from(0x2000).to_string(), "Vendor B");
+        assert!(Machine::unregister_name(0x2000));
+        assert!(!Machine::unregister_name(0x2000));
+        assert_eq!(Machine::from(0x2000).to_string(), "Machine 0x2000");
+
+        // Known names can't be changed.
+        assert!(!Machine::register_name(Machine::X86_64, "Vendor"));
+        assert_eq!(Machine::from(Machine::X86_64).to_string(), "AMD x86-64");
+        assert!(!Machine::register_name(Machine::MCORE, "Vendor"));
+        assert!(!Machine::register_name(0xFEED, "Vendor"));
+    }
+
     #[test]
     fn abi_string_reserved_values() {
         abi_string_reserved_range(11..=14);
@@ -1559,4 +1674,28 @@ mod tests {
 
         assert_eq!(machine.machine(), Machine::ARM);
     }
+
+    #[test]
+    fn max_page_size() {
+        assert_eq!(Machine::from(Machine::X86_64).max_page_size(), Some(0x1000));
+        assert_eq!(
+            Machine::from(Machine::AARCH64).max_page_size(),
+            Some(0x10000)
+        );
+        assert_eq!(Machine::from(Machine::NONE).max_page_size(), None);
+    }
+
+    #[test]
+    fn map_key() {
+        let mut count = std::collections::BTreeMap::new();
+        for m in [Machine::X86_64, Machine::ARM, Machine::X86_64] {
+            *count.entry(Machine::from(m)).or_insert(0) += 1;
+        }
+        let count: Vec<(u16, u32)> = count.into_iter().map(|(m, c)| (m.into(), c)).collect();
+        assert_eq!(count, vec![(Machine::ARM, 1), (Machine::X86_64, 2)]);
+
+        let set: std::collections::HashSet<Machine> =
+            [Machine::from(Machine::ARM), Machine::from(Machine::ARM)].into();
+        assert_eq!(set.len(), 1);
+    }
 }
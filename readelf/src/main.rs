@@ -0,0 +1,477 @@
+use clap::{CommandFactory, Parser, ValueEnum, ValueHint};
+use clap_complete::Shell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::{IsTerminal, Read, Write as IoWrite};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use readelf::*;
+
+/// Exit code if a file is not an ELF file.
+const EXIT_NOT_ELF: u8 = 1;
+
+/// Exit code if a file can't be read. The command line parser already uses 2
+/// for invalid arguments.
+const EXIT_IO_ERROR: u8 = 3;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum Format {
+    /// Text similar to GNU readelf.
+    Text,
+
+    /// A JSON document, or an array with a document for each file if there
+    /// are several files.
+    Json,
+
+    /// A YAML document for each file.
+    Yaml,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum Color {
+    /// Never colour the output.
+    Never,
+
+    /// Always colour the output.
+    Always,
+
+    /// Colour the output if it is a terminal.
+    Auto,
+}
+
+#[derive(Parser, Debug)]
+#[command(disable_help_flag = true)]
+struct Args {
+    /// Show help.
+    #[arg(short = '?', long = "help", default_value_t = false)]
+    help: bool,
+
+    /// Show the ELF header (usually first 52/64 bytes).
+    #[arg(short = 'h', long = "file-header", default_value_t = false)]
+    headers: bool,
+
+    /// Show the program headers (segments).
+    #[arg(short = 'l', long = "segments", default_value_t = false)]
+    segments: bool,
+
+    /// Use the same wording as GNU readelf for the OS ABI and the machine.
+    #[arg(long = "gnu", default_value_t = false)]
+    gnu: bool,
+
+    /// Don't wrap the output to fit in 80 columns.
+    #[arg(short = 'W', long = "wide", default_value_t = false)]
+    wide: bool,
+
+    /// Equivalent to: -h -l
+    #[arg(short = 'e', long = "headers", default_value_t = false)]
+    all_headers: bool,
+
+    /// Show everything that is supported. Equivalent to: -h -l
+    #[arg(short = 'a', long = "all", default_value_t = false)]
+    all: bool,
+
+    /// The format of the output.
+    #[arg(long = "format", value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// When to colour the text output.
+    #[arg(long = "color", value_enum, default_value_t = Color::Auto)]
+    color: Color,
+
+    /// Show one line for each file with the class, machine, type and
+    /// interpreter.
+    #[arg(long = "summary", default_value_t = false)]
+    summary: bool,
+
+    /// Show the fields of the template for each file, e.g.
+    /// `%{file}: %{machine} %{entry}\n`. The fields are `file`, `class`,
+    /// `data`, `version`, `osabi`, `abi_version`, `type`, `machine`, `entry`,
+    /// `flags`, `phoff`, `phnum`, `shoff`, `shnum` and `interpreter`.
+    #[arg(long = "printf")]
+    printf: Option<String>,
+
+    /// Show the text, data and bss sizes like `size(1)`.
+    #[arg(long = "size", default_value_t = false)]
+    size: bool,
+
+    /// Show the printable strings in the loadable segments that are not
+    /// executable.
+    #[arg(long = "strings", default_value_t = false)]
+    strings: bool,
+
+    /// The minimum length of a string for --strings.
+    #[arg(long = "min-len", default_value_t = 4)]
+    min_len: usize,
+
+    /// Show the differences between two ELF files.
+    #[arg(long = "diff", default_value_t = false)]
+    diff: bool,
+
+    /// Print the completions for the shell given and exit.
+    #[arg(long = "completions", value_enum)]
+    completions: Option<Shell>,
+
+    /// Read all ELF files in the directories given, and their subdirectories.
+    #[arg(short = 'R', long = "recursive", default_value_t = false)]
+    recursive: bool,
+
+    /// Write the output to the file given instead of the standard output.
+    #[arg(short = 'o', long = "output", value_hint = ValueHint::FilePath)]
+    output: Option<PathBuf>,
+
+    /// The number of files to read in parallel. The output is always in the
+    /// order of the files. Defaults to the number of CPUs.
+    #[arg(short = 'j', long = "jobs")]
+    jobs: Option<NonZeroUsize>,
+
+    /// A list of files that should be read. Glob patterns (e.g. `lib/*.so`)
+    /// are expanded. Use `-` to read from the standard input.
+    #[arg(trailing_var_arg = true, value_hint = ValueHint::FilePath)]
+    files: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let mut cli = Args::parse();
+    if cli.help {
+        let _ = Args::command().print_long_help();
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some(shell) = cli.completions {
+        let mut command = Args::command();
+        clap_complete::generate(shell, &mut command, "readelf", &mut std::io::stdout());
+        return ExitCode::SUCCESS;
+    }
+
+    if cli.all_headers || cli.all {
+        cli.headers = true;
+        cli.segments = true;
+    }
+
+    let mut output: Box<dyn IoWrite> = match &cli.output {
+        Some(path) => match std::fs::File::create(path) {
+            Ok(f) => Box::new(std::io::BufWriter::new(f)),
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                return ExitCode::from(EXIT_IO_ERROR);
+            }
+        },
+        None => Box::new(std::io::stdout()),
+    };
+
+    let options = display::Options {
+        wide: cli.wide,
+        gnu: cli.gnu,
+        color: match cli.color {
+            Color::Never => false,
+            Color::Always => true,
+            Color::Auto => cli.output.is_none() && std::io::stdout().is_terminal(),
+        },
+    };
+
+    if cli.diff {
+        return diff_files(&cli.files, &mut output);
+    }
+
+    // The highest exit code of all files read, so that an I/O error is reported
+    // over a file that is not an ELF file.
+    let mut status = 0;
+    let files = inputs(&cli.files, cli.recursive, &mut status);
+
+    // Once writing the output fails, the remaining files are not read.
+    let mut written = Ok(());
+
+    // The number of JSON documents printed as elements of an array.
+    let mut documents = 0;
+    if cli.size && !files.is_empty() {
+        written = writeln!(output, "{}", display::SIZE_HEADER);
+    }
+
+    let jobs = cli
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get)
+        .min(files.len());
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let (files, next, cli, options) = (&files, &next, &cli, &options);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(file) = files.get(index) else {
+                    break;
+                };
+                let r = report(file, cli, options, files.len() > 1);
+                if tx.send((index, r)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        // Print the reports in the order of the files, as they're finished.
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+        for (index, r) in rx {
+            if written.is_err() {
+                break;
+            }
+            pending.insert(index, r);
+            while let Some(r) = pending.remove(&printed) {
+                if written.is_ok() {
+                    written = if r.json && files.len() > 1 {
+                        let separator = if documents == 0 { "[" } else { "," };
+                        documents += 1;
+                        writeln!(output, "{}", separator)
+                            .and_then(|_| output.write_all(r.out.trim_end().as_bytes()))
+                    } else {
+                        output.write_all(r.out.as_bytes())
+                    };
+                }
+                eprint!("{}", r.err);
+                status = status.max(r.status);
+                printed += 1;
+            }
+        }
+    });
+
+    if documents > 0 {
+        written = written.and_then(|_| writeln!(output, "\n]"));
+    }
+    if let Err(e) = written.and_then(|_| output.flush()) {
+        eprintln!("{}", e);
+        status = status.max(EXIT_IO_ERROR);
+    }
+    ExitCode::from(status)
+}
+
+/// The output of reading a single file.
+struct Report {
+    out: String,
+    err: String,
+    status: u8,
+
+    /// The output is a JSON document, which is an element of an array if
+    /// there are several files.
+    json: bool,
+}
+
+/// Read a single file, and format the output as requested.
+fn report(file: &Path, cli: &Args, options: &display::Options, multiple: bool) -> Report {
+    let mut r = Report {
+        out: String::new(),
+        err: String::new(),
+        status: 0,
+        json: false,
+    };
+
+    let elf = match open(file) {
+        Ok(elf) => elf,
+        Err(e) => {
+            let _ = writeln!(r.err, "{}: {}", file.display(), error_message(&e));
+            r.status = error_status(&e);
+            return r;
+        }
+    };
+
+    let name = file.display().to_string();
+    if let Some(template) = &cli.printf {
+        match display::format_template(&elf, &name, template) {
+            Ok(text) => r.out.push_str(&text),
+            Err(e) => {
+                let _ = writeln!(r.err, "--printf: {}", e);
+                r.status = 2;
+            }
+        }
+        return r;
+    }
+
+    if cli.summary {
+        let _ = writeln!(r.out, "{}", display::format_summary(&elf, &name, options));
+        return r;
+    }
+
+    if cli.size {
+        let _ = writeln!(r.out, "{}", display::format_size(&elf.size_report(), &name));
+        return r;
+    }
+
+    match cli.format {
+        Format::Text => {}
+        Format::Json => {
+            let _ = writeln!(r.out, "{}", elf.to_json());
+            r.json = true;
+            return r;
+        }
+        Format::Yaml => {
+            // Each file is a document of the stream.
+            let _ = write!(r.out, "---\n{}", elf.to_yaml());
+            return r;
+        }
+    }
+
+    if multiple {
+        let _ = writeln!(r.out);
+        let _ = writeln!(r.out, "File: {}", name);
+    }
+
+    if cli.strings {
+        for ph in elf.segments_of_type(SegmentType::Load) {
+            if !ph.flags.is_executable() {
+                r.out.push_str(&display::format_strings(
+                    &elf,
+                    ph.file_offset,
+                    ph.file_size,
+                    cli.min_len,
+                ));
+            }
+        }
+        return r;
+    }
+
+    if cli.headers {
+        r.out
+            .push_str(&display::format_file_header_with(&elf, options));
+    }
+
+    if cli.segments {
+        r.out
+            .push_str(&display::format_program_headers_with(&elf, options));
+    }
+    r
+}
+
+/// Print the differences between exactly two ELF files.
+fn diff_files(files: &[String], output: &mut dyn IoWrite) -> ExitCode {
+    let [a, b] = files else {
+        eprintln!("--diff needs two files");
+        return ExitCode::from(2);
+    };
+
+    let mut elf = Vec::new();
+    for file in [a, b] {
+        match open(Path::new(file)) {
+            Ok(r) => elf.push(r),
+            Err(e) => {
+                eprintln!("{}: {}", file, error_message(&e));
+                return ExitCode::from(error_status(&e));
+            }
+        }
+    }
+
+    let text = display::format_diff(&diff(&elf[0], &elf[1]));
+    if let Err(e) = output
+        .write_all(text.as_bytes())
+        .and_then(|_| output.flush())
+    {
+        eprintln!("{}", e);
+        return ExitCode::from(EXIT_IO_ERROR);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Open the file, where `-` is the standard input.
+///
+/// Files are read on demand. Only the standard input, which can't be seeked,
+/// is read into memory.
+fn open(path: &Path) -> Result<ReadElf<'static>, ParseError> {
+    if path == Path::new("-") {
+        let mut buffer = Vec::new();
+        std::io::stdin()
+            .lock()
+            .read_to_end(&mut buffer)
+            .map_err(|e| ParseError::Io(e.kind()))?;
+        ReadElf::from_vec_with_options(buffer, ReadOptions::default())
+    } else {
+        ReadElf::open_with_options(path, ReadOptions::default())
+    }
+}
+
+/// Get the message to print for an error of [open()].
+fn error_message(e: &ParseError) -> String {
+    match e {
+        ParseError::Io(kind) => kind.to_string(),
+        _ => "not an ELF file".to_string(),
+    }
+}
+
+/// Get the exit code for an error of [open()].
+fn error_status(e: &ParseError) -> u8 {
+    match e {
+        ParseError::Io(_) => EXIT_IO_ERROR,
+        _ => EXIT_NOT_ELF,
+    }
+}
+
+/// Get the files to read from the command line.
+///
+/// Glob patterns are expanded, and directories are walked if `recursive` is
+/// set. Files found this way that are not ELF files are skipped.
+fn inputs(files: &[String], recursive: bool, status: &mut u8) -> Vec<PathBuf> {
+    let mut result = Vec::new();
+    for file in files {
+        let path = PathBuf::from(file);
+        if path.exists() || !file.contains(['*', '?', '[']) {
+            input(path, recursive, false, status, &mut result);
+            continue;
+        }
+
+        match glob::glob(file) {
+            Ok(paths) => {
+                for path in paths.flatten() {
+                    input(path, recursive, true, status, &mut result);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", file, e);
+                *status = (*status).max(EXIT_IO_ERROR);
+            }
+        }
+    }
+    result
+}
+
+fn input(path: PathBuf, recursive: bool, sniff: bool, status: &mut u8, result: &mut Vec<PathBuf>) {
+    if !path.is_dir() {
+        if !sniff || is_elf(&path) {
+            result.push(path);
+        }
+        return;
+    }
+
+    if !recursive {
+        eprintln!("{}: is a directory, use -R to read it", path.display());
+        *status = (*status).max(EXIT_IO_ERROR);
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(&path) else {
+        eprintln!("{}: can't read directory", path.display());
+        *status = (*status).max(EXIT_IO_ERROR);
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        // Don't follow symbolic links to directories, they could loop.
+        if path.is_symlink() && path.is_dir() {
+            continue;
+        }
+        input(path, recursive, true, status, result);
+    }
+}
+
+/// Check the magic of the file, without reading the complete file.
+fn is_elf(path: &Path) -> bool {
+    let mut magic = [0; 4];
+    match std::fs::File::open(path) {
+        Ok(mut f) => f.read_exact(&mut magic).is_ok() && magic == *b"\x7fELF",
+        Err(_) => false,
+    }
+}
@@ -15,7 +15,7 @@ use std::fmt;
 /// let o = OsAbi::from(OsAbi::NONE);
 /// println!("{:?}", o);
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct OsAbi {
     os_abi: u8,
 }
@@ -99,14 +99,12 @@ impl OsAbi {
 }
 
 impl From<u8> for OsAbi {
-    #[must_use]
     fn from(v: u8) -> Self {
         OsAbi { os_abi: v }
     }
 }
 
 impl From<OsAbi> for u8 {
-    #[must_use]
     fn from(v: OsAbi) -> Self {
         v.os_abi
     }
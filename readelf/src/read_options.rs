@@ -0,0 +1,73 @@
+/// Limits when reading an ELF file from an untrusted source.
+///
+/// The ELF file is read lazily, but some values from the file, such as the
+/// number of entries in a header table or the size of a segment, determine how
+/// much is read or allocated. Services reading ELF files from untrusted sources
+/// can use these limits to reject files that would need excessive resources.
+///
+/// The default values have no limits. Apply the limits with
+/// [ReadElf::with_options()](crate::ReadElf::with_options), or when reading
+/// the ELF file with one of the constructors such as
+/// [ReadElf::open_with_options()](crate::ReadElf::open_with_options).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::{ReadElf, ReadOptions};
+///
+/// let options = ReadOptions {
+///     max_program_headers: 64,
+///     max_section_headers: 4096,
+///     max_allocation: 1 << 20,
+///     ..Default::default()
+/// };
+/// match ReadElf::open_with_options("upload.bin", options) {
+///     Ok(elf) => println!("{:?}", elf),
+///     Err(e) => println!("Rejected: {}", e),
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadOptions {
+    /// The maximum number of entries in the program header table (`e_phnum`,
+    /// or `sh_info` of the first section header if there are more program
+    /// headers than fit in the ELF header).
+    pub max_program_headers: u32,
+
+    /// The maximum number of entries in the section header table (`e_shnum`,
+    /// or `sh_size` of the first section header if there are more sections
+    /// than fit in the ELF header).
+    pub max_section_headers: u64,
+
+    /// The maximum number of bytes allocated when reading a single element of
+    /// the ELF file, e.g. the path of the program interpreter, or the contents
+    /// of a segment.
+    ///
+    /// Elements that are larger are handled as if they can't be read. This is
+    /// a limit for each read, not for the total: a method that reads many
+    /// elements, such as all notes or all strings of the loadable segments,
+    /// may allocate more in total.
+    pub max_allocation: u64,
+
+    /// Only accept ELF files with the version `1` (`EV_CURRENT`) in
+    /// `e_version`.
+    ///
+    /// Some toolchains write a different version in `e_version` in otherwise
+    /// valid ELF files. If `false`, these files are accepted, and the version
+    /// is given in [ReadElf::version](crate::ReadElf::version). The version
+    /// of the identification, `e_ident[EI_VERSION]`, must always be `1`.
+    ///
+    /// This is only used by the constructors with options, such as
+    /// [ReadElf::open_with_options()](crate::ReadElf::open_with_options).
+    pub strict_version: bool,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        ReadOptions {
+            max_program_headers: u32::MAX,
+            max_section_headers: u64::MAX,
+            max_allocation: u64::MAX,
+            strict_version: true,
+        }
+    }
+}
@@ -1,13 +1,64 @@
 use crate::binparser;
-use crate::{Class, Endian, ExecutableType, Machine, OsAbi};
+use crate::{
+    Class, Endian, ExecutableType, Machine, OsAbi, ParseError, ReadOptions, SegmentFlags,
+    SegmentType,
+};
 use std::fmt;
+use std::ops::Range;
 use std::path::Path;
+use std::sync::Arc;
+
+mod android;
+pub use android::{AndroidDynamic, AndroidIdent};
+
+mod arm_exidx;
+pub use arm_exidx::{ArmExceptionIndex, ArmUnwind};
+
+mod binary_image;
+pub use binary_image::{BinaryImage, BinaryImageOptions};
+
+mod byte_search;
+use byte_search::BytePattern;
+
+mod go_build_info;
+pub use go_build_info::{GoBuildInfo, GoModule};
+
+mod hex_record;
+
+mod module_signature;
+pub use module_signature::ModuleSignature;
+
+mod note;
+pub use note::Note;
+
+mod package_metadata;
+pub use package_metadata::PackageMetadata;
+
+mod packer;
+pub use packer::{PackerIndicator, PackerReport};
 
 mod program_header;
 pub use program_header::ProgramHeader;
 
 mod program_headers;
-pub use program_headers::ProgramHeaders;
+pub use program_headers::{ProgramHeaders, ProgramHeadersChecked, ProgramHeadersIndexed};
+
+#[cfg(feature = "serde")]
+mod json;
+
+mod raw_header;
+pub use raw_header::RawHeader;
+
+mod reproducibility;
+pub use reproducibility::ReproducibilityReport;
+
+mod segments;
+pub use segments::Segments;
+
+mod strings;
+
+mod size_report;
+pub use size_report::{SegmentSize, SizeReport};
 
 /// Properties of an ELF file when loaded into memory.
 ///
@@ -27,7 +78,10 @@ pub struct ReadElf<'elf> {
 
     /// The ELF version, which is expected to always be `1`.
     ///
-    /// This value represents `e_ident[EI_VERSION] or e_version`.
+    /// Other values are only accepted if [ReadOptions::strict_version] is
+    /// `false`.
+    ///
+    /// This value represents `e_version`.
     pub version: u32,
 
     /// The OS ABI specification for the ELF file, interpreted by the OS.
@@ -70,7 +124,7 @@ pub struct ReadElf<'elf> {
     /// Bytes for 32-bit format.
     ///
     /// This value represents `e_ehsize`.
-    file_header_size: u16,
+    pub(crate) file_header_size: u16,
 
     /// Points to the start of the program header table.
     ///
@@ -79,40 +133,43 @@ pub struct ReadElf<'elf> {
     /// respectively.
     ///
     /// This value represents `e_phoff`.
-    program_header_offset: u64,
+    pub(crate) program_header_offset: u64,
 
     /// Contains the size of a program header table entry.
     ///
     /// This value represents `e_phentsize`.
-    program_header_size: u16,
+    pub(crate) program_header_size: u16,
 
     /// Contains the number of entries in the program header table.
     ///
     /// This value represents `e_phnum`.
-    program_header_count: u16,
+    pub(crate) program_header_count: u16,
 
     /// Points to the start of the section header table.
     ///
     /// This value represents `e_shoff`.
-    section_header_offset: u64,
+    pub(crate) section_header_offset: u64,
 
     /// Contains the size of a section header table entry.
     ///
     /// This value represents `e_shentsize`.
-    section_header_size: u16,
+    pub(crate) section_header_size: u16,
 
     /// Contains the number of entries in the section header table.
     ///
     /// This value represents `e_shnum`.
-    section_header_count: u16,
+    pub(crate) section_header_count: u16,
 
     /// Contains index of the section header table entry that contains the
     /// section names.
     ///
     /// This value repesents `e_shstrndx`.
-    string_section_index: u16,
+    pub(crate) string_section_index: u16,
 
-    parser: Box<dyn binparser::BinParser + 'elf>,
+    /// The limits when reading the ELF file.
+    options: ReadOptions,
+
+    parser: Box<dyn binparser::BinParser + Send + 'elf>,
 }
 
 impl<'elf> fmt::Debug for ReadElf<'elf> {
@@ -168,9 +225,9 @@ impl<'elf> ReadElf<'elf> {
         }
     }
 
-    fn from_parser<T>(p: Box<T>) -> Option<ReadElf<'elf>>
+    fn from_parser<T>(p: Box<T>, strict_version: bool) -> Option<ReadElf<'elf>>
     where
-        T: binparser::BinParser + 'elf,
+        T: binparser::BinParser + Send + 'elf,
     {
         // The signature of the ELF must file be 0x7F ELF.
         if p.get_u8(0)? != 0x7F
@@ -184,8 +241,11 @@ impl<'elf> ReadElf<'elf> {
         // The endianness is needed often when interpreting.
         let e = Endian::try_from(p.get_u8(5)?).ok()?;
 
-        // We only support Version 1 when reading.
-        if p.get_u8(6)? != 1 || p.get_u32(20, e)? != 1 {
+        // We only support Version 1 when reading. The caller may accept other
+        // versions of `e_version`, but the version of the identification
+        // `e_ident[EI_VERSION]` must always be 1.
+        let version = p.get_u32(20, e)?;
+        if p.get_u8(6)? != 1 || (strict_version && version != 1) {
             return None;
         }
 
@@ -195,7 +255,7 @@ impl<'elf> ReadElf<'elf> {
         Some(ReadElf::<'elf> {
             class: c,
             data: e,
-            version: 1,
+            version,
             osabi: OsAbi::from(p.get_u8(7)?),
             abi_version: p.get_u8(8)?,
             exec_type: ExecutableType::from(p.get_u16(16, e)?),
@@ -210,17 +270,49 @@ impl<'elf> ReadElf<'elf> {
             section_header_size: p.get_u16(ReadElf::offset(c, 46, 58), e)?,
             section_header_count: p.get_u16(ReadElf::offset(c, 48, 60), e)?,
             string_section_index: p.get_u16(ReadElf::offset(c, 50, 62), e)?,
+            options: ReadOptions::default(),
             parser: p,
         })
     }
 
+    /// Interpret the ELF file with a parser, and apply the options.
+    fn from_parser_with_options<T>(
+        p: Box<T>,
+        options: ReadOptions,
+    ) -> Result<ReadElf<'elf>, ParseError>
+    where
+        T: binparser::BinParser + Send + 'elf,
+    {
+        Self::from_parser(p, options.strict_version)
+            .ok_or(ParseError::NotElf)?
+            .with_options(options)
+    }
+
     /// Interpret the ELF file from a buffer slice in memory.
     ///
     /// This method is useful if you have guarantees over the lifetime of the
     /// ELF file, that it lasts longer than the [ReadElf] object you get back.
     pub fn from_slice(buffer: &'elf [u8]) -> Option<ReadElf<'elf>> {
         let p = Box::new(binparser::Slice::<'elf>::new(buffer));
-        Self::from_parser(p)
+        Self::from_parser(p, true)
+    }
+
+    /// Interpret the ELF file from a buffer slice in memory, with the options
+    /// given.
+    ///
+    /// See [ReadElf::from_slice()] and [ReadElf::with_options()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [ParseError::NotElf] if the contents are not an ELF file, or
+    /// the error of [ReadElf::with_options()] if the limits of the options
+    /// are exceeded.
+    pub fn from_slice_with_options(
+        buffer: &'elf [u8],
+        options: ReadOptions,
+    ) -> Result<ReadElf<'elf>, ParseError> {
+        let p = Box::new(binparser::Slice::<'elf>::new(buffer));
+        Self::from_parser_with_options(p, options)
     }
 
     /// Interpret the ELF file from a buffer in memory.
@@ -229,7 +321,52 @@ impl<'elf> ReadElf<'elf> {
     /// the heap inside the [ReadElf] object.
     pub fn from_vec(buffer: Vec<u8>) -> Option<ReadElf<'elf>> {
         let p = Box::new(binparser::VecBuffer::new(buffer));
-        Self::from_parser(p)
+        Self::from_parser(p, true)
+    }
+
+    /// Interpret the ELF file from a buffer in memory, with the options given.
+    ///
+    /// See [ReadElf::from_vec()] and [ReadElf::with_options()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [ParseError::NotElf] if the contents are not an ELF file, or
+    /// the error of [ReadElf::with_options()] if the limits of the options
+    /// are exceeded.
+    pub fn from_vec_with_options(
+        buffer: Vec<u8>,
+        options: ReadOptions,
+    ) -> Result<ReadElf<'elf>, ParseError> {
+        let p = Box::new(binparser::VecBuffer::new(buffer));
+        Self::from_parser_with_options(p, options)
+    }
+
+    /// Interpret the ELF file from a shared buffer in memory.
+    ///
+    /// The buffer is shared with the [ReadElf] object without copying. This
+    /// allows multiple threads to each read the same ELF file in memory, by
+    /// creating an instance in each thread from a clone of the [Arc].
+    pub fn from_arc(buffer: Arc<[u8]>) -> Option<ReadElf<'elf>> {
+        let p = Box::new(binparser::ArcBuffer::new(buffer));
+        Self::from_parser(p, true)
+    }
+
+    /// Interpret the ELF file from a shared buffer in memory, with the options
+    /// given.
+    ///
+    /// See [ReadElf::from_arc()] and [ReadElf::with_options()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [ParseError::NotElf] if the contents are not an ELF file, or
+    /// the error of [ReadElf::with_options()] if the limits of the options
+    /// are exceeded.
+    pub fn from_arc_with_options(
+        buffer: Arc<[u8]>,
+        options: ReadOptions,
+    ) -> Result<ReadElf<'elf>, ParseError> {
+        let p = Box::new(binparser::ArcBuffer::new(buffer));
+        Self::from_parser_with_options(p, options)
     }
 
     /// Interpret the ELF file from disk.
@@ -237,14 +374,903 @@ impl<'elf> ReadElf<'elf> {
     /// This method opens the file on disk and uses seeks to access the file.
     /// This allows to open very large ELF files also on 32-bit systems.
     pub fn open<P: AsRef<Path>>(path: P) -> Option<ReadElf<'elf>> {
-        let p = Box::new(binparser::File::open(path)?);
-        Self::from_parser(p)
+        let p = Box::new(binparser::File::open(path).ok()?);
+        Self::from_parser(p, true)
+    }
+
+    /// Interpret the ELF file from disk, with the options given.
+    ///
+    /// See [ReadElf::open()] and [ReadElf::with_options()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [ParseError::Io] if the file can't be opened,
+    /// [ParseError::NotElf] if the contents are not an ELF file, or the error
+    /// of [ReadElf::with_options()] if the limits of the options are exceeded.
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: ReadOptions,
+    ) -> Result<ReadElf<'elf>, ParseError> {
+        let p = binparser::File::open(path).map_err(|e| ParseError::Io(e.kind()))?;
+        let p = Box::new(p);
+        Self::from_parser_with_options(p, options)
+    }
+
+    /// Get the identification bytes `e_ident` at the start of the ELF file.
+    pub(crate) fn ident(&self) -> Option<[u8; 16]> {
+        let mut ident = [0; 16];
+        self.parser.read_into(0, &mut ident)?;
+        Some(ident)
+    }
+
+    /// Get the byte at the offset in the ELF file.
+    pub(crate) fn byte(&self, offset: u64) -> Option<u8> {
+        self.parser.get_u8(offset)
+    }
+
+    /// Read a range of the file into memory.
+    ///
+    /// The range is checked against the end of the file and
+    /// [ReadOptions::max_allocation] before the memory is allocated, as the
+    /// range usually comes from a field of the file.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the range ends after the end of the file, is
+    /// larger than [ReadOptions::max_allocation], or can't be read.
+    pub(crate) fn read_bytes(&self, range: Range<u64>) -> Option<Vec<u8>> {
+        let len = range.end.checked_sub(range.start)?;
+        if len > self.options.max_allocation || range.end > self.parser.len()? {
+            return None;
+        }
+
+        let mut bytes = vec![0; usize::try_from(len).ok()?];
+        self.parser.read_into(range.start, &mut bytes)?;
+        Some(bytes)
+    }
+
+    /// Read a NUL terminated string from a range of the file.
+    ///
+    /// The string is read in small chunks, so that only the bytes up to the
+    /// NUL character are read, however large the range is.
+    ///
+    /// # Returns
+    ///
+    /// Returns the bytes before the NUL character. Returns [Option::None] if
+    /// there is no NUL character in the range, or it can't be read.
+    pub(crate) fn read_string(&self, range: Range<u64>) -> Option<Vec<u8>> {
+        const CHUNK_SIZE: usize = 0x100;
+
+        let end = match self.parser.len() {
+            Some(len) => range.end.min(len),
+            None => range.end,
+        };
+        let mut s = Vec::new();
+        let mut chunk = [0; CHUNK_SIZE];
+        let mut offset = range.start;
+        while offset < end {
+            // Can't truncate, it's at most the size of the chunk.
+            let len = (end - offset).min(CHUNK_SIZE as u64) as usize;
+            self.parser.read_into(offset, &mut chunk[..len])?;
+            if let Some(nul) = chunk[..len].iter().position(|b| *b == 0) {
+                s.extend_from_slice(&chunk[..nul]);
+                return Some(s);
+            }
+            s.extend_from_slice(&chunk[..len]);
+            offset += len as u64;
+        }
+        None
+    }
+
+    /// Read a range of the file in chunks, calling `f` with the offset and
+    /// the contents of each chunk.
+    ///
+    /// The range is limited to the end of the file. Each chunk is read at
+    /// once, instead of byte by byte, and only one chunk is in memory.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if a chunk can't be read.
+    pub(crate) fn read_chunks<F>(&self, range: Range<u64>, mut f: F) -> Option<()>
+    where
+        F: FnMut(u64, &[u8]),
+    {
+        const CHUNK_SIZE: u64 = 0x10000;
+
+        let end = match self.parser.len() {
+            Some(len) => range.end.min(len),
+            None => range.end,
+        };
+        let mut chunk =
+            vec![0; usize::try_from(CHUNK_SIZE.min(end.saturating_sub(range.start))).ok()?];
+        let mut offset = range.start;
+        while offset < end {
+            // Can't truncate, it's at most the size of the chunk.
+            let len = (end - offset).min(CHUNK_SIZE) as usize;
+            self.parser.read_into(offset, &mut chunk[..len])?;
+            f(offset, &chunk[..len]);
+            offset += len as u64;
+        }
+        Some(())
+    }
+
+    /// Get the entries of the dynamic segments as tag and value pairs.
+    ///
+    /// The entries of a segment are read up to `DT_NULL`, or the first entry
+    /// that can't be read. Segments larger than [ReadOptions::max_allocation]
+    /// are skipped.
+    pub(crate) fn dynamic_entries(&self) -> Vec<(u64, u64)> {
+        const DT_NULL: u64 = 0;
+
+        let entry_size = ReadElf::offset(self.class, 8, 16);
+        let word_size = entry_size / 2;
+        let mut entries = Vec::new();
+        for ph in self.segments_of_type(SegmentType::Dynamic) {
+            if ph.file_size > self.options.max_allocation {
+                continue;
+            }
+            for entry in 0..ph.file_size / entry_size {
+                let Some(offset) = ph.file_offset.checked_add(entry * entry_size) else {
+                    break;
+                };
+                let Some(tag) = self.parser.get_usize(offset, self.data, self.class) else {
+                    break;
+                };
+                if tag == DT_NULL {
+                    break;
+                }
+                let Some(value) = offset
+                    .checked_add(word_size)
+                    .and_then(|o| self.parser.get_usize(o, self.data, self.class))
+                else {
+                    break;
+                };
+                entries.push((tag, value));
+            }
+        }
+        entries
+    }
+
+    /// Check if the file is a position independent executable.
+    ///
+    /// This is the case if the flag `DF_1_PIE` is set in the entry
+    /// `DT_FLAGS_1` of the dynamic segment.
+    pub(crate) fn is_pie(&self) -> bool {
+        const DT_FLAGS_1: u64 = 0x6FFFFFFB;
+        const DF_1_PIE: u64 = 0x08000000;
+
+        self.dynamic_entries()
+            .into_iter()
+            .find(|(tag, _)| *tag == DT_FLAGS_1)
+            .is_some_and(|(_, v)| v & DF_1_PIE != 0)
+    }
+
+    /// Get a copy of the [ReadElf] object that reads the same ELF file.
+    ///
+    /// The ELF header is not parsed again, and the copy can be given to
+    /// another thread. A buffer from [ReadElf::from_slice()] or
+    /// [ReadElf::from_arc()] is shared, a buffer from [ReadElf::from_vec()] is
+    /// copied, and the handle of a file from [ReadElf::open()] is duplicated,
+    /// so that the copy reads the same file, even if it was renamed or
+    /// replaced on disk.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the handle of the file can't be duplicated.
+    pub fn try_clone(&self) -> Option<ReadElf<'elf>> {
+        Some(ReadElf {
+            class: self.class,
+            data: self.data,
+            version: self.version,
+            osabi: self.osabi,
+            abi_version: self.abi_version,
+            exec_type: self.exec_type,
+            machine: self.machine,
+            entry: self.entry,
+            flags: self.flags,
+            file_header_size: self.file_header_size,
+            program_header_offset: self.program_header_offset,
+            program_header_size: self.program_header_size,
+            program_header_count: self.program_header_count,
+            section_header_offset: self.section_header_offset,
+            section_header_size: self.section_header_size,
+            section_header_count: self.section_header_count,
+            string_section_index: self.string_section_index,
+            options: self.options,
+            parser: self.parser.clone_parser()?,
+        })
+    }
+
+    /// Apply limits for reading the ELF file from an untrusted source.
+    ///
+    /// The number of program headers and section headers is checked
+    /// immediately. The maximum allocation is checked when an element of the
+    /// file is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [ParseError::ProgramHeaderLimit] or
+    /// [ParseError::SectionHeaderLimit] if the ELF file has more entries in
+    /// the header tables than allowed by the options.
+    pub fn with_options(mut self, options: ReadOptions) -> Result<ReadElf<'elf>, ParseError> {
+        let segments = self.segment_count().unwrap_or_default();
+        if segments > options.max_program_headers {
+            return Err(ParseError::ProgramHeaderLimit(segments));
+        }
+
+        let sections = self.section_count().unwrap_or_default();
+        if sections > options.max_section_headers {
+            return Err(ParseError::SectionHeaderLimit(sections));
+        }
+
+        self.options = options;
+        Ok(self)
+    }
+
+    /// Get the limits for reading the ELF file.
+    pub fn options(&self) -> ReadOptions {
+        self.options
+    }
+
+    /// Get the fields of the ELF header that describe the layout of the file.
+    ///
+    /// This gives access to `e_phoff`, `e_shoff`, `e_ehsize`, `e_phentsize`,
+    /// `e_phnum`, `e_shentsize`, `e_shnum` and `e_shstrndx` without parsing
+    /// the ELF header again.
+    pub fn raw_header(&self) -> RawHeader {
+        RawHeader::new(self)
+    }
+
+    /// Get the offset of a field in the first section header.
+    ///
+    /// The first section header (index 0) is reserved, and is used to store
+    /// values that don't fit in the ELF header. Returns [Option::None] if
+    /// there is no section header table, or the entry size is too small.
+    fn section_zero_offset(&self, o32: u64, o64: u64) -> Option<u64> {
+        let min_shentsize = match self.class {
+            Class::Elf32 => 40,
+            Class::Elf64 => 64,
+        };
+        if self.section_header_offset == 0 || self.section_header_size < min_shentsize {
+            return None;
+        }
+        self.section_header_offset
+            .checked_add(ReadElf::offset(self.class, o32, o64))
+    }
+
+    /// Get the number of entries in the program header table.
+    ///
+    /// If `e_phnum` is `PN_XNUM`, the number of entries is read from `sh_info`
+    /// of the first section header.
+    pub(crate) fn segment_count(&self) -> Option<u32> {
+        const PN_XNUM: u16 = 0xFFFF;
+
+        match self.program_header_count {
+            PN_XNUM => {
+                // The `sh_info` field of the section header at index 0.
+                let offset = self.section_zero_offset(28, 44)?;
+                self.parser.get_u32(offset, self.data)
+            }
+            v => Some(u32::from(v)),
+        }
+    }
+
+    /// Get the number of entries in the section header table.
+    ///
+    /// If `e_shnum` is zero, the number of entries is read from `sh_size` of
+    /// the first section header.
+    fn section_count(&self) -> Option<u64> {
+        match self.section_header_count {
+            0 => {
+                // The `sh_size` field of the section header at index 0.
+                let offset = self.section_zero_offset(20, 32)?;
+                self.parser.get_usize(offset, self.data, self.class)
+            }
+            v => Some(v as u64),
+        }
+    }
+
+    /// Get the index of the section containing the section names.
+    ///
+    /// This is the value of `e_shstrndx`. If the index doesn't fit in the ELF
+    /// header (`e_shstrndx` is `SHN_XINDEX`), the index is read from the
+    /// `sh_link` field of the first section header.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no section name string table
+    /// (`e_shstrndx` is `SHN_UNDEF`), or if the index is `SHN_XINDEX` and the
+    /// first section header can't be read.
+    pub fn string_section_index(&self) -> Option<u32> {
+        const SHN_UNDEF: u16 = 0;
+        const SHN_XINDEX: u16 = 0xFFFF;
+
+        match self.string_section_index {
+            SHN_UNDEF => None,
+            SHN_XINDEX => {
+                // The `sh_link` field of the section header at index 0.
+                let offset = self.section_zero_offset(24, 40)?;
+                self.parser.get_u32(offset, self.data)
+            }
+            v => Some(v as u32),
+        }
+    }
+
+    /// Get the range of bytes in the file containing the ELF header.
+    ///
+    /// This is the range `0..e_ehsize`.
+    pub fn file_header_range(&self) -> Range<u64> {
+        0..self.file_header_size as u64
+    }
+
+    /// Get the range of bytes in the file containing the program header
+    /// table.
+    ///
+    /// This is `e_phoff` for `e_phentsize * e_phnum` bytes. If there are too
+    /// many program headers for the ELF header (`e_phnum` is `PN_XNUM`), the
+    /// number of entries is read from `sh_info` of the first section header.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no program header table, or the
+    /// range is not representable.
+    pub fn program_header_range(&self) -> Option<Range<u64>> {
+        if self.program_header_offset == 0 {
+            return None;
+        }
+
+        let count = self.segment_count()?;
+        if count == 0 {
+            return None;
+        }
+
+        // Can't overflow, a 16-bit value multiplied with a 32-bit value.
+        let length = u64::from(count) * self.program_header_size as u64;
+        let end = self.program_header_offset.checked_add(length)?;
+        Some(self.program_header_offset..end)
+    }
+
+    /// Get the range of bytes in the file containing the section header
+    /// table.
+    ///
+    /// This is `e_shoff` for `e_shentsize * e_shnum` bytes. If there are too
+    /// many sections for the ELF header (`e_shnum` is zero), the number of
+    /// entries is read from `sh_size` of the first section header.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no section header table, or the
+    /// range is not representable.
+    pub fn section_header_range(&self) -> Option<Range<u64>> {
+        let count = self.section_count()?;
+        if self.section_header_offset == 0 || count == 0 {
+            return None;
+        }
+
+        let length = count.checked_mul(self.section_header_size as u64)?;
+        let end = self.section_header_offset.checked_add(length)?;
+        Some(self.section_header_offset..end)
+    }
+
+    /// Get the loadable segments that can't be mapped with pages of
+    /// `page_size`.
+    ///
+    /// See [ProgramHeader::is_page_aligned()]. A binary with such segments
+    /// fails to load on a kernel with larger pages, a common problem for
+    /// [Machine::AARCH64] binaries linked for 4KiB pages running on kernels
+    /// with 16KiB or 64KiB pages. The segments are given with their index in
+    /// the program header table.
+    pub fn misaligned_segments(&self, page_size: u64) -> Vec<(usize, ProgramHeader)> {
+        self.program_headers()
+            .enumerate()
+            .filter(|(_, ph)| ph.is_loadable() && !ph.is_page_aligned(page_size))
+            .collect()
+    }
+
+    /// Get the gaps in the file between the contents of the segments.
+    ///
+    /// The file image of the segments, the ELF header and the program header
+    /// table are merged, and the ranges of bytes between them are returned in
+    /// the order of the file. This is mostly padding to align the loadable
+    /// segments. Sections are not read, so the gaps include the contents of
+    /// sections that are not in a segment, e.g. `.comment`. The data after the
+    /// last segment, such as the section header table, is not a gap.
+    pub fn segment_gaps(&self) -> Vec<Range<u64>> {
+        let mut ranges: Vec<Range<u64>> = self
+            .program_headers()
+            .filter_map(|ph| ph.file_range())
+            .chain([self.file_header_range()])
+            .chain(self.program_header_range())
+            .filter(|r| !r.is_empty())
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+
+        let mut gaps = Vec::new();
+        let mut end = match ranges.first() {
+            Some(r) => r.start,
+            None => return gaps,
+        };
+        for r in ranges {
+            if r.start > end {
+                gaps.push(end..r.start);
+            }
+            end = end.max(r.end);
+        }
+        gaps
     }
 
     /// Get an iterator for all the program headers in the ELF file.
     pub fn program_headers(&'elf self) -> ProgramHeaders<'elf> {
         ProgramHeaders::new(self)
     }
+
+    /// Get an iterator for the program headers of a specific [SegmentType].
+    ///
+    /// The segment types are compared by their value, so that
+    /// `SegmentType::Unknown(1)` is the same as [SegmentType::Load].
+    pub fn segments_of_type(
+        &'elf self,
+        segment_type: SegmentType,
+    ) -> impl Iterator<Item = ProgramHeader> + 'elf {
+        self.program_headers()
+            .filter(move |ph| ph.is_type(segment_type))
+    }
+
+    /// Get the program header table, that can be iterated multiple times.
+    pub fn segments(&'elf self) -> Segments<'elf> {
+        Segments::new(self)
+    }
+
+    /// Get the offset in the file of a virtual address.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the address is not in the file image of a
+    /// [SegmentType::Load] segment, e.g. it's in the bss.
+    pub fn vaddr_to_offset(&self, vaddr: u64) -> Option<u64> {
+        self.segments_of_type(SegmentType::Load).find_map(|ph| {
+            let offset = vaddr.checked_sub(ph.virtual_address)?;
+            if offset >= ph.file_size {
+                return None;
+            }
+            ph.file_offset.checked_add(offset)
+        })
+    }
+
+    /// Get the strings of the dynamic string table (`DT_STRTAB`) for the
+    /// entries of the dynamic segment with the tag.
+    fn dynamic_strings(&self, tag: u64) -> Vec<String> {
+        const DT_STRTAB: u64 = 5;
+        const DT_STRSZ: u64 = 10;
+
+        let entries = self.dynamic_entries();
+        let find = |t: u64| entries.iter().find(|(e, _)| *e == t).map(|(_, v)| *v);
+        let Some(strtab) = find(DT_STRTAB).and_then(|v| self.vaddr_to_offset(v)) else {
+            return Vec::new();
+        };
+        let size = find(DT_STRSZ).unwrap_or(u64::MAX);
+
+        entries
+            .iter()
+            .filter(|(t, _)| *t == tag)
+            .filter_map(|(_, index)| {
+                // The NUL character must be within max_allocation bytes.
+                let len = size.checked_sub(*index)?.min(self.options.max_allocation);
+                let start = strtab.checked_add(*index)?;
+                let s = self.read_string(start..start.checked_add(len)?)?;
+                Some(String::from_utf8_lossy(&s).into_owned())
+            })
+            .collect()
+    }
+
+    /// Get the names of the shared objects that the ELF file depends on.
+    ///
+    /// These are the `DT_NEEDED` entries of the dynamic segment, in the order
+    /// they are loaded. Names that can't be read are skipped.
+    pub fn needed_libraries(&self) -> Vec<String> {
+        const DT_NEEDED: u64 = 1;
+        self.dynamic_strings(DT_NEEDED)
+    }
+
+    /// Get the name of the shared object, `DT_SONAME`.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no `DT_SONAME` entry, or it can't be
+    /// read.
+    pub fn soname(&self) -> Option<String> {
+        const DT_SONAME: u64 = 14;
+        self.dynamic_strings(DT_SONAME).into_iter().next()
+    }
+
+    /// Get the library search path `DT_RPATH`, as a colon separated list.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no `DT_RPATH` entry, or it can't be
+    /// read.
+    pub fn rpath(&self) -> Option<String> {
+        const DT_RPATH: u64 = 15;
+        self.dynamic_strings(DT_RPATH).into_iter().next()
+    }
+
+    /// Get the library search path `DT_RUNPATH`, as a colon separated list.
+    ///
+    /// If present, `DT_RPATH` is ignored by the dynamic loader.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no `DT_RUNPATH` entry, or it can't
+    /// be read.
+    pub fn runpath(&self) -> Option<String> {
+        const DT_RUNPATH: u64 = 29;
+        self.dynamic_strings(DT_RUNPATH).into_iter().next()
+    }
+
+    /// Get the path of the program interpreter.
+    ///
+    /// This is the contents of the [SegmentType::Interpreter] segment, up to
+    /// the terminating NUL character. Bytes that are not valid UTF-8 are
+    /// replaced with `U+FFFD`.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no interpreter segment, it can't be
+    /// read, or it is larger than [ReadOptions::max_allocation].
+    pub fn interpreter(&self) -> Option<String> {
+        let ph = self.segments_of_type(SegmentType::Interpreter).next()?;
+        let path = self.read_bytes(ph.file_range()?)?;
+        let path = path.split(|b| *b == 0).next().unwrap_or_default();
+        Some(String::from_utf8_lossy(path).into_owned())
+    }
+
+    /// Get the entries of the ARM exception index table (`.ARM.exidx`).
+    ///
+    /// The table is found with the `PT_ARM_EXIDX` segment for the
+    /// [Machine::ARM]. Each entry covers the functions from its address up to
+    /// the address of the next entry. Reading stops at the first entry that
+    /// can't be read.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the ELF file is not for [Machine::ARM], there
+    /// is no `PT_ARM_EXIDX` segment, or it is larger than
+    /// [ReadOptions::max_allocation].
+    pub fn arm_exception_index(&self) -> Option<Vec<ArmExceptionIndex>> {
+        ArmExceptionIndex::read(self)
+    }
+
+    /// Get the notes of all the [SegmentType::Note] segments.
+    ///
+    /// Reading a segment stops at the first note that can't be read, or that
+    /// is larger than [ReadOptions::max_allocation].
+    pub fn notes(&self) -> Vec<Note> {
+        Note::read_all(self)
+    }
+
+    /// Get the build ID from the `NT_GNU_BUILD_ID` note.
+    ///
+    /// The build ID identifies the build of the ELF file, and is usually a
+    /// hash of its contents written by the linker.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no such note.
+    pub fn build_id(&self) -> Option<Vec<u8>> {
+        const NT_GNU_BUILD_ID: u32 = 3;
+
+        self.notes()
+            .into_iter()
+            .find(|n| n.name == "GNU" && n.note_type == NT_GNU_BUILD_ID)
+            .map(|n| n.desc)
+    }
+
+    /// Get the package metadata from the `NT_FDO_PACKAGING_METADATA` note.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no such note. If the note is not a
+    /// valid JSON object, or the known keys don't have string values, only
+    /// [PackageMetadata::json] is set.
+    pub fn package_metadata(&self) -> Option<PackageMetadata> {
+        PackageMetadata::read(self)
+    }
+
+    /// Get the Android identification from the `.note.android.ident` note.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no such note, e.g. the binary
+    /// wasn't built with the Android NDK.
+    pub fn android_ident(&self) -> Option<AndroidIdent> {
+        AndroidIdent::read(self)
+    }
+
+    /// Get the entries of the dynamic segment that are specific to Android.
+    ///
+    /// These are the packed relocations of Bionic. As the tags overlap with
+    /// those of other operating systems, they are only given for an Android
+    /// binary, which has the `.note.android.ident` note or uses the
+    /// `/system/bin/linker` interpreter. Otherwise the result is empty.
+    pub fn android_dynamic(&self) -> Vec<AndroidDynamic> {
+        AndroidDynamic::read(self)
+    }
+
+    /// Get the build information embedded in a Go binary.
+    ///
+    /// This is the Go version, the main module, its dependencies and the build
+    /// settings, as printed by `go version -m`. The information is searched
+    /// for in the writable data segments, as sections are not read.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the ELF file is not a Go binary, or it was
+    /// built with Go older than 1.18 which doesn't embed the strings in the
+    /// build information.
+    pub fn go_build_info(&self) -> Option<GoBuildInfo> {
+        GoBuildInfo::read(self)
+    }
+
+    /// Get the signature appended to a Linux kernel module.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the file doesn't end with the marker
+    /// `~Module signature appended~`, i.e. the module is not signed, or if the
+    /// signature information is not consistent with the size of the file.
+    pub fn module_signature(&self) -> Option<ModuleSignature> {
+        ModuleSignature::read(self)
+    }
+
+    /// Get the permissions of the stack, from the `PT_GNU_STACK` segment.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there is no `PT_GNU_STACK` segment.
+    pub fn stack_flags(&self) -> Option<SegmentFlags> {
+        const PT_GNU_STACK: u32 = 0x6474E551;
+
+        self.segments_of_type(SegmentType::from(PT_GNU_STACK))
+            .next()
+            .map(|ph| ph.flags)
+    }
+
+    /// Check if the stack of the program is executable.
+    ///
+    /// The stack is executable if the `PT_GNU_STACK` segment has
+    /// [SegmentFlags::X]. Without the segment, the stack of an executable or
+    /// shared object is implicitly executable, except for architectures where
+    /// the Linux kernel and glibc default to a non-executable stack
+    /// ([Machine::AARCH64], [Machine::RISCV] and [Machine::LOONGARCH]). Other
+    /// files, such as relocatable objects, have no stack.
+    pub fn is_stack_executable(&self) -> bool {
+        if let Some(flags) = self.stack_flags() {
+            return flags.is_executable();
+        }
+
+        let loadable = matches!(
+            self.exec_type,
+            ExecutableType::Executable | ExecutableType::Dynamic
+        );
+        let non_exec_default = [Machine::AARCH64, Machine::RISCV, Machine::LOONGARCH]
+            .contains(&u16::from(self.machine));
+        loadable && !non_exec_default
+    }
+
+    /// Get the loadable segment that contains the entry point
+    /// [ReadElf::entry].
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if no [SegmentType::Load] segment contains the
+    /// entry point, e.g. a shared object without an entry point.
+    pub fn entry_segment(&self) -> Option<ProgramHeader> {
+        self.segments_of_type(SegmentType::Load)
+            .find(|ph| ph.contains_vaddr(self.entry))
+    }
+
+    /// Check if the entry point is in an executable loadable segment.
+    ///
+    /// An entry point outside of the executable segments of an executable is
+    /// suspicious, as it's a common sign of an infected binary. Without
+    /// symbols and sections, the entry point can't be checked against `_start`
+    /// or the `.text` section.
+    pub fn is_entry_executable(&self) -> bool {
+        self.entry_segment()
+            .is_some_and(|ph| ph.flags.is_executable())
+    }
+
+    /// Get the lowest virtual address of the loadable segments.
+    ///
+    /// This is the virtual address of the first [SegmentType::Load] segment,
+    /// rounded down to its alignment. It's where the ELF file is mapped in
+    /// memory, when it's loaded at the addresses it was linked for.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there are no loadable segments.
+    pub fn image_base(&self) -> Option<u64> {
+        self.segments_of_type(SegmentType::Load)
+            .map(|ph| match ph.alignment.is_power_of_two() {
+                true => ph.virtual_address & !(ph.alignment - 1),
+                false => ph.virtual_address,
+            })
+            .min()
+    }
+
+    /// Get the load bias of the ELF file when it's loaded at a base address.
+    ///
+    /// A position independent executable or shared object
+    /// ([ExecutableType::Dynamic]) is loaded at an arbitrary address, e.g. as
+    /// shown in `/proc/<pid>/maps` or the mappings of a core file. The load
+    /// bias is the difference from `load_base` to [ReadElf::image_base()],
+    /// which is added to the virtual addresses of the file, such as
+    /// [ReadElf::entry] or [ProgramHeader::virtual_address], to get the
+    /// addresses at runtime. The arithmetic wraps, so a file loaded below the
+    /// address it was linked for has a bias that is a two's complement
+    /// negative number.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there are no loadable segments.
+    pub fn load_bias(&self, load_base: u64) -> Option<u64> {
+        Some(load_base.wrapping_sub(self.image_base()?))
+    }
+
+    /// Translate a virtual address of the ELF file to the address at runtime,
+    /// when the file is loaded at `load_base`.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there are no loadable segments.
+    pub fn runtime_address(&self, vaddr: u64, load_base: u64) -> Option<u64> {
+        Some(vaddr.wrapping_add(self.load_bias(load_base)?))
+    }
+
+    /// Translate an address at runtime to the virtual address of the ELF file,
+    /// when the file is loaded at `load_base`.
+    ///
+    /// This is the reverse of [ReadElf::runtime_address()].
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there are no loadable segments.
+    pub fn file_address(&self, addr: u64, load_base: u64) -> Option<u64> {
+        Some(addr.wrapping_sub(self.load_bias(load_base)?))
+    }
+
+    /// Get the `size(1)` style text, data and bss sizes of the ELF file.
+    ///
+    /// The sizes are calculated from the loadable segments. See [SizeReport]
+    /// for details.
+    pub fn size_report(&self) -> SizeReport {
+        SizeReport::new(self)
+    }
+
+    /// Get the absolute paths embedded in the ELF file, with their offset in
+    /// the file.
+    ///
+    /// The paths are searched for in the strings of the loadable segments that
+    /// are not writable, which contain the read-only data (`.rodata`) and the
+    /// dynamic string table. A path must have at least two components, e.g.
+    /// `/bin/sh`. As sections are not read, paths in the debug information
+    /// (`.debug_str`) or the symbol table are not found.
+    pub fn embedded_paths(&self) -> Vec<(u64, String)> {
+        strings::read_only_strings(self, strings::MIN_PATH_LEN)
+            .iter()
+            .flat_map(|(offset, s)| {
+                strings::absolute_paths(s).map(move |(i, p)| (offset + i as u64, p.to_string()))
+            })
+            .collect()
+    }
+
+    /// Check the ELF file for sources of non-determinism of the build.
+    ///
+    /// This looks for the build ID, absolute paths of build directories and
+    /// timestamps in the read-only data. See [ReproducibilityReport] for
+    /// details.
+    pub fn reproducibility_report(&self) -> ReproducibilityReport {
+        ReproducibilityReport::new(self)
+    }
+
+    /// Find a byte pattern in the contents of the loadable segments.
+    ///
+    /// If a `mask` is given, only the bits set in the mask are compared, e.g.
+    /// a mask of `0x00` matches any byte. The segments are read in chunks, so
+    /// the whole file isn't loaded in memory. As sections are not read, the
+    /// contents that are not in a loadable segment, such as the debug
+    /// information, are not searched. Use [ReadElf::find_bytes_in()] to search
+    /// a selected segment.
+    ///
+    /// # Returns
+    ///
+    /// The offset in the file and the virtual address of each match, in the
+    /// order of the segments. Matches may overlap.
+    ///
+    /// Returns [Option::None] if the pattern is empty, or the mask has a
+    /// different length than the pattern.
+    pub fn find_bytes(&self, pattern: &[u8], mask: Option<&[u8]>) -> Option<Vec<(u64, u64)>> {
+        let pattern = BytePattern::new(pattern, mask)?;
+        Some(
+            self.segments_of_type(SegmentType::Load)
+                .flat_map(|ph| pattern.find_in(self, &ph))
+                .collect(),
+        )
+    }
+
+    /// Find a byte pattern in the contents of the segment in the file.
+    ///
+    /// The segment is usually one of [ReadElf::program_headers()], but can be
+    /// any range of the file. See [ReadElf::find_bytes()] for details.
+    ///
+    /// # Returns
+    ///
+    /// The offset in the file and the virtual address of each match. Matches
+    /// may overlap.
+    ///
+    /// Returns [Option::None] if the pattern is empty, or the mask has a
+    /// different length than the pattern.
+    pub fn find_bytes_in(
+        &self,
+        segment: &ProgramHeader,
+        pattern: &[u8],
+        mask: Option<&[u8]>,
+    ) -> Option<Vec<(u64, u64)>> {
+        Some(BytePattern::new(pattern, mask)?.find_in(self, segment))
+    }
+
+    /// Check the ELF file for signs of a packer, such as UPX.
+    ///
+    /// This looks for the UPX magic, writable and executable segments,
+    /// segments that are expanded at runtime, an entry point outside of the
+    /// executable segments, and segments with a high entropy. The contents of
+    /// each loadable segment are read once. See [PackerReport] for details.
+    pub fn packer_report(&self) -> PackerReport {
+        PackerReport::new(self)
+    }
+
+    /// Create a flat memory image of the loadable segments, like
+    /// `objcopy -O binary`.
+    ///
+    /// See [BinaryImage] for details.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if there are no loadable segments with contents
+    /// in the file, a segment is below [BinaryImageOptions::base] or ends after
+    /// the end of the file, the image is larger than
+    /// [BinaryImageOptions::max_size] or [ReadOptions::max_allocation], the
+    /// memory for the image can't be allocated, or a segment can't be read.
+    pub fn binary_image(&self, options: &BinaryImageOptions) -> Option<BinaryImage> {
+        BinaryImage::new(self, options)
+    }
+
+    /// Serialize the contents of the ELF file as a JSON document.
+    ///
+    /// The document has the object `header` with the fields of the ELF header,
+    /// and the array `segments` with the program headers that can be read.
+    /// Enumerated values, such as the machine, are objects with the numeric
+    /// `value` and the `name` as given by [fmt::Display].
+    ///
+    /// This method is only available with the feature `serde`.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        // The document only contains strings and numbers, which can always be
+        // serialized.
+        serde_json::to_string_pretty(&json::Document::new(self))
+            .expect("JSON serialization of the ELF document")
+    }
+
+    /// Serialize the contents of the ELF file as a YAML document.
+    ///
+    /// The document has the same structure as [ReadElf::to_json()].
+    ///
+    /// This method is only available with the feature `serde`.
+    #[cfg(feature = "serde")]
+    pub fn to_yaml(&self) -> String {
+        serde_yaml_ng::to_string(&json::Document::new(self))
+            .expect("YAML serialization of the ELF document")
+    }
 }
 
 #[cfg(test)]
@@ -318,4 +1344,19 @@ mod tests {
         assert_eq!(r.entry, 0x1001ABC8);
         assert_eq!(r.flags, 0x00000000);
     }
+
+    #[test]
+    fn powerpc_exe_bash_raw_header() {
+        let r = test_resource(&["elf", "debian-8.11.0-powerpc-netinst", "bash"]);
+        let h = r.raw_header();
+
+        assert_eq!(h.program_header_offset, 52);
+        assert_eq!(h.section_header_offset, 1026776);
+        assert_eq!(h.file_header_size, 52);
+        assert_eq!(h.program_header_size, 32);
+        assert_eq!(h.program_header_count, 9);
+        assert_eq!(h.section_header_size, 40);
+        assert_eq!(h.section_header_count, 30);
+        assert_eq!(h.string_section_index, 29);
+    }
 }
@@ -0,0 +1,133 @@
+use crate::{Endian, ReadElf};
+
+/// The owner of the Android notes.
+const ANDROID_NOTE_NAME: &str = "Android";
+
+/// The note type of the `.note.android.ident` note.
+const NT_ANDROID_TYPE_IDENT: u32 = 1;
+
+/// The size of the NDK version and build number strings in the note.
+const ANDROID_NDK_STRING_SIZE: usize = 64;
+
+/// The identification of an Android binary, from `.note.android.ident`.
+///
+/// Get an instance with [ReadElf::android_ident()].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AndroidIdent {
+    /// The minimum Android API level the binary was built for.
+    pub api_level: u32,
+
+    /// The version of the NDK that built the binary, e.g. `r26b`.
+    ///
+    /// The NDK writes this since r14.
+    pub ndk_version: Option<String>,
+
+    /// The build number of the NDK that built the binary.
+    ///
+    /// The NDK writes this since r14.
+    pub ndk_build_number: Option<String>,
+}
+
+impl AndroidIdent {
+    pub(super) fn read(elf: &ReadElf<'_>) -> Option<AndroidIdent> {
+        let note = elf
+            .notes()
+            .into_iter()
+            .find(|n| n.name == ANDROID_NOTE_NAME && n.note_type == NT_ANDROID_TYPE_IDENT)?;
+
+        let api_level: [u8; 4] = note.desc.get(0..4)?.try_into().ok()?;
+        let api_level = match elf.data {
+            Endian::Little => u32::from_le_bytes(api_level),
+            Endian::Big => u32::from_be_bytes(api_level),
+        };
+
+        let ndk_version_end = 4 + ANDROID_NDK_STRING_SIZE;
+        let ndk_build_number_end = ndk_version_end + ANDROID_NDK_STRING_SIZE;
+        Some(AndroidIdent {
+            api_level,
+            ndk_version: note.desc.get(4..ndk_version_end).map(c_string),
+            ndk_build_number: note
+                .desc
+                .get(ndk_version_end..ndk_build_number_end)
+                .map(c_string),
+        })
+    }
+}
+
+/// An entry of the dynamic segment that is specific to Android (Bionic).
+///
+/// Get the entries with [ReadElf::android_dynamic()].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AndroidDynamic {
+    /// The tag of the entry, e.g. [AndroidDynamic::DT_ANDROID_RELR].
+    pub tag: u64,
+
+    /// The value of the entry.
+    pub value: u64,
+}
+
+impl AndroidDynamic {
+    /// Address of the packed `REL` relocations (`APS2` format).
+    pub const DT_ANDROID_REL: u64 = 0x6000000F;
+
+    /// Size of the packed `REL` relocations.
+    pub const DT_ANDROID_RELSZ: u64 = 0x60000010;
+
+    /// Address of the packed `RELA` relocations (`APS2` format).
+    pub const DT_ANDROID_RELA: u64 = 0x60000011;
+
+    /// Size of the packed `RELA` relocations.
+    pub const DT_ANDROID_RELASZ: u64 = 0x60000012;
+
+    /// Address of the relative relocations (`RELR` format), before `DT_RELR`
+    /// was standardised.
+    pub const DT_ANDROID_RELR: u64 = 0x6FFFE000;
+
+    /// Size of the relative relocations.
+    pub const DT_ANDROID_RELRSZ: u64 = 0x6FFFE001;
+
+    /// Size of an entry of the relative relocations.
+    pub const DT_ANDROID_RELRENT: u64 = 0x6FFFE003;
+
+    /// Number of relative relocations.
+    pub const DT_ANDROID_RELRCOUNT: u64 = 0x6FFFE005;
+
+    /// Get the name of the tag, e.g. `ANDROID_RELR`, as GNU readelf shows it.
+    pub fn name(&self) -> &'static str {
+        match self.tag {
+            AndroidDynamic::DT_ANDROID_REL => "ANDROID_REL",
+            AndroidDynamic::DT_ANDROID_RELSZ => "ANDROID_RELSZ",
+            AndroidDynamic::DT_ANDROID_RELA => "ANDROID_RELA",
+            AndroidDynamic::DT_ANDROID_RELASZ => "ANDROID_RELASZ",
+            AndroidDynamic::DT_ANDROID_RELR => "ANDROID_RELR",
+            AndroidDynamic::DT_ANDROID_RELRSZ => "ANDROID_RELRSZ",
+            AndroidDynamic::DT_ANDROID_RELRENT => "ANDROID_RELRENT",
+            AndroidDynamic::DT_ANDROID_RELRCOUNT => "ANDROID_RELRCOUNT",
+            _ => "",
+        }
+    }
+
+    pub(super) fn read(elf: &ReadElf<'_>) -> Vec<AndroidDynamic> {
+        // The tags overlap with tags of other operating systems, e.g. Solaris,
+        // so they're only interpreted for Android.
+        let android = elf.android_ident().is_some()
+            || elf
+                .interpreter()
+                .is_some_and(|i| i.starts_with("/system/bin/linker"));
+        if !android {
+            return Vec::new();
+        }
+
+        elf.dynamic_entries()
+            .into_iter()
+            .map(|(tag, value)| AndroidDynamic { tag, value })
+            .filter(|d| !d.name().is_empty())
+            .collect()
+    }
+}
+
+/// Get the string up to the first NUL character.
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
@@ -0,0 +1,102 @@
+use crate::{Machine, ReadElf, SegmentType};
+
+/// The segment type `PT_ARM_EXIDX` with the `.ARM.exidx` section.
+const PT_ARM_EXIDX: u32 = 0x70000001;
+
+/// The value of the second word of an entry if the function can't unwind.
+const EXIDX_CANTUNWIND: u32 = 1;
+
+/// How the stack is unwound for a function in the ARM exception index table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArmUnwind {
+    /// The function can't be unwound, `EXIDX_CANTUNWIND`.
+    CantUnwind,
+
+    /// The unwind instructions are in the compact model inside the entry.
+    ///
+    /// The value is the second word of the entry, with the bit 31 set and the
+    /// personality routine index in bits 24 to 27.
+    Inline(u32),
+
+    /// The virtual address of the entry in the `.ARM.extab` table.
+    Table(u64),
+}
+
+/// An entry of the ARM exception index table (`.ARM.exidx`) for AArch32.
+///
+/// Get the entries with [ReadElf::arm_exception_index()].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArmExceptionIndex {
+    /// The virtual address of the first function covered by this entry.
+    ///
+    /// The entry covers all functions up to the address of the next entry.
+    pub function: u64,
+
+    /// How the stack is unwound for the function.
+    pub unwind: ArmUnwind,
+}
+
+impl ArmExceptionIndex {
+    pub(super) fn read(elf: &ReadElf<'_>) -> Option<Vec<ArmExceptionIndex>> {
+        if elf.machine != Machine::from(Machine::ARM) {
+            return None;
+        }
+
+        let ph = elf
+            .segments_of_type(SegmentType::from(PT_ARM_EXIDX))
+            .next()?;
+        if ph.file_size > elf.options.max_allocation {
+            return None;
+        }
+
+        let mut entries = Vec::new();
+        for entry in 0..ph.file_size / 8 {
+            let Some(offset) = ph.file_offset.checked_add(entry * 8) else {
+                break;
+            };
+            let Some(function) = elf.parser.get_u32(offset, elf.data) else {
+                break;
+            };
+            let Some(unwind) = offset
+                .checked_add(4)
+                .and_then(|o| elf.parser.get_u32(o, elf.data))
+            else {
+                break;
+            };
+
+            let place = ph.virtual_address.wrapping_add(entry * 8);
+            let unwind = if unwind == EXIDX_CANTUNWIND {
+                ArmUnwind::CantUnwind
+            } else if unwind & 0x80000000 != 0 {
+                ArmUnwind::Inline(unwind)
+            } else {
+                ArmUnwind::Table(prel31(place.wrapping_add(4), unwind))
+            };
+            entries.push(ArmExceptionIndex {
+                function: prel31(place, function),
+                unwind,
+            });
+        }
+        Some(entries)
+    }
+}
+
+/// Get the address of a 31-bit offset relative to the `place` it is stored.
+fn prel31(place: u64, value: u32) -> u64 {
+    // Sign extend from bit 30.
+    let offset = ((value << 1) as i32) >> 1;
+    place.wrapping_add_signed(i64::from(offset)) & 0xFFFFFFFF
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prel31;
+
+    #[test]
+    fn prel31_offset() {
+        assert_eq!(prel31(0x1000, 0x00000010), 0x1010);
+        assert_eq!(prel31(0x1000, 0x7FFFFFF0), 0x0FF0);
+        assert_eq!(prel31(0x1000, 0xFFFFFFF0), 0x0FF0);
+        assert_eq!(prel31(0x10, 0x7FFFFFE0), 0xFFFFFFF0);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::ReadElf;
+
+/// Options to create a [BinaryImage].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::{BinaryImageOptions, ReadElf};
+///
+/// let elf = ReadElf::open("firmware.elf").unwrap();
+/// let options = BinaryImageOptions {
+///     fill: 0xFF,
+///     max_size: 256 * 1024,
+///     ..Default::default()
+/// };
+/// if let Some(image) = elf.binary_image(&options) {
+///     std::fs::write("firmware.bin", &image.data).unwrap();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BinaryImageOptions {
+    /// The address of the first byte of the image.
+    ///
+    /// If [Option::None], the image starts at the lowest physical address of
+    /// the loadable segments.
+    pub base: Option<u64>,
+
+    /// The byte to fill the gaps between the segments with.
+    pub fill: u8,
+
+    /// The maximum size of the image in bytes.
+    pub max_size: u64,
+}
+
+impl Default for BinaryImageOptions {
+    fn default() -> Self {
+        BinaryImageOptions {
+            base: None,
+            fill: 0,
+            max_size: u64::MAX,
+        }
+    }
+}
+
+/// A flat memory image of the loadable segments, like `objcopy -O binary`.
+///
+/// The contents of each loadable segment ([crate::SegmentType::Load]) in the
+/// file is placed at its physical address, which is the load address in flash
+/// for embedded targets. The memory that is not in the file, such as the bss,
+/// is not part of the image. Get an instance with [ReadElf::binary_image()].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BinaryImage {
+    /// The address of the first byte of the image.
+    pub base: u64,
+
+    /// The contents of the image.
+    pub data: Vec<u8>,
+
+    /// The entry point of the program, [ReadElf::entry].
+    pub entry: u64,
+}
+
+impl BinaryImage {
+    pub(super) fn new(elf: &ReadElf<'_>, options: &BinaryImageOptions) -> Option<BinaryImage> {
+        let segments: Vec<_> = elf
+            .program_headers()
+            .filter(|ph| ph.is_loadable() && ph.file_size > 0)
+            .collect();
+
+        let base = match options.base {
+            Some(base) => base,
+            None => segments.iter().map(|ph| ph.physical_address).min()?,
+        };
+        // The segments are checked against the size of the file before the
+        // image is allocated.
+        let file_len = elf.parser.len()?;
+        let mut end = base;
+        for ph in &segments {
+            if ph.physical_address < base || ph.file_range()?.end > file_len {
+                return None;
+            }
+            end = end.max(ph.physical_address.checked_add(ph.file_size)?);
+        }
+
+        let size = end - base;
+        if size > options.max_size || size > elf.options.max_allocation {
+            return None;
+        }
+
+        // The gaps between the segments can make the image much larger than
+        // the file, so an allocation that fails isn't fatal.
+        let size = usize::try_from(size).ok()?;
+        let mut data = Vec::new();
+        data.try_reserve_exact(size).ok()?;
+        data.resize(size, options.fill);
+        for ph in &segments {
+            // Can't overflow, the segment is within the image.
+            let start = usize::try_from(ph.physical_address - base).ok()?;
+            let end = start + usize::try_from(ph.file_size).ok()?;
+            elf.parser
+                .read_into(ph.file_offset, &mut data[start..end])?;
+        }
+        Some(BinaryImage {
+            base,
+            data,
+            entry: elf.entry,
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{ProgramHeader, ReadElf};
+
+/// A byte pattern, where only the bits set in the mask are compared.
+pub(super) struct BytePattern<'p> {
+    pattern: &'p [u8],
+    mask: Option<&'p [u8]>,
+}
+
+impl<'p> BytePattern<'p> {
+    /// Create a pattern with an optional mask of the same length.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the pattern is empty, or the mask has a
+    /// different length.
+    pub(super) fn new(pattern: &'p [u8], mask: Option<&'p [u8]>) -> Option<BytePattern<'p>> {
+        if pattern.is_empty() || mask.is_some_and(|m| m.len() != pattern.len()) {
+            return None;
+        }
+        Some(BytePattern { pattern, mask })
+    }
+
+    fn matches(&self, bytes: &[u8]) -> bool {
+        match self.mask {
+            Some(mask) => self
+                .pattern
+                .iter()
+                .zip(mask)
+                .zip(bytes)
+                .all(|((p, m), b)| p & m == b & m),
+            None => self.pattern == bytes,
+        }
+    }
+
+    /// Find the pattern in the contents of the segment in the file, reading
+    /// the file in chunks.
+    ///
+    /// Matches may overlap. If the segment is truncated, the contents up to
+    /// the end of the file are searched.
+    ///
+    /// # Returns
+    ///
+    /// The offset in the file and the virtual address of each match.
+    pub(super) fn find_in(&self, elf: &ReadElf<'_>, ph: &ProgramHeader) -> Vec<(u64, u64)> {
+        let mut found = Vec::new();
+        let Some(range) = ph.file_range() else {
+            return found;
+        };
+
+        // The end of the previous chunk is kept, to find a match across the
+        // boundary of the chunks.
+        let keep = self.pattern.len() - 1;
+        let mut buffer = Vec::new();
+        let mut start = range.start;
+        let _ = elf.read_chunks(range, |_, chunk| {
+            buffer.extend_from_slice(chunk);
+            for (i, window) in buffer.windows(self.pattern.len()).enumerate() {
+                if self.matches(window) {
+                    let file_offset = start + i as u64;
+                    let vaddr = ph
+                        .virtual_address
+                        .wrapping_add(file_offset - ph.file_offset);
+                    found.push((file_offset, vaddr));
+                }
+            }
+
+            let drain = buffer.len().saturating_sub(keep);
+            buffer.drain(..drain);
+            start += drain as u64;
+        });
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BytePattern;
+
+    #[test]
+    fn pattern_mask() {
+        assert!(BytePattern::new(&[], None).is_none());
+        assert!(BytePattern::new(&[1, 2], Some(&[0xFF])).is_none());
+
+        let pattern = BytePattern::new(&[0xE8, 0x00, 0x12], Some(&[0xFF, 0x00, 0xF0])).unwrap();
+        assert!(pattern.matches(&[0xE8, 0x34, 0x1F]));
+        assert!(!pattern.matches(&[0xE9, 0x34, 0x1F]));
+        assert!(!pattern.matches(&[0xE8, 0x34, 0x20]));
+
+        let pattern = BytePattern::new(&[0xE8, 0x00], None).unwrap();
+        assert!(pattern.matches(&[0xE8, 0x00]));
+        assert!(!pattern.matches(&[0xE8, 0x01]));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{ProgramHeader, ReadElf, SegmentType};
+
+/// The magic at the start of the Go build information.
+const BUILD_INFO_MAGIC: &[u8] = b"\xff Go buildinf:";
+
+/// The build information is aligned to 16 bytes.
+const BUILD_INFO_ALIGN: u64 = 16;
+
+/// The size of the header, before the version and module strings.
+const BUILD_INFO_HEADER_SIZE: u64 = 32;
+
+/// The flag in the header, that the strings follow the header.
+const FLAGS_VERSION_INL: u8 = 0x2;
+
+/// The segment type `PT_GNU_RELRO`, of data that is read-only after
+/// relocation.
+const PT_GNU_RELRO: u32 = 0x6474E552;
+
+/// A Go module, from the build information of a Go binary.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GoModule {
+    /// The module path, e.g. `golang.org/x/sys`.
+    pub path: String,
+
+    /// The version of the module, e.g. `v0.20.0`, or `(devel)`.
+    pub version: String,
+
+    /// The checksum of the module, may be empty.
+    pub sum: String,
+
+    /// The module that replaces this module, if a `replace` directive was used.
+    pub replace: Option<Box<GoModule>>,
+}
+
+/// The build information embedded in a Go binary.
+///
+/// This is the information that `go version -m` prints. Get an instance with
+/// [ReadElf::go_build_info()].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GoBuildInfo {
+    /// The version of the Go toolchain that built the binary, e.g.
+    /// `go1.22.3`.
+    pub go_version: String,
+
+    /// The package path of the main package.
+    pub path: String,
+
+    /// The main module.
+    pub main: Option<GoModule>,
+
+    /// The module dependencies.
+    pub deps: Vec<GoModule>,
+
+    /// The build settings, such as `GOOS` or `-ldflags`, as key value pairs.
+    pub settings: Vec<(String, String)>,
+}
+
+impl GoBuildInfo {
+    pub(super) fn read(elf: &ReadElf<'_>) -> Option<GoBuildInfo> {
+        let header = find_header(elf)?;
+
+        // Older versions of Go (before 1.18) store pointers to the strings,
+        // which aren't supported.
+        let flags = elf.parser.get_u8(header + 15)?;
+        if flags & FLAGS_VERSION_INL == 0 {
+            return None;
+        }
+
+        // Both strings are limited together, as they're read at once.
+        let mut offset = header + BUILD_INFO_HEADER_SIZE;
+        let version = read_string(elf, &mut offset, elf.options.max_allocation)?;
+        let remaining = elf.options.max_allocation - version.len() as u64;
+        let modinfo = read_string(elf, &mut offset, remaining)?;
+
+        // The module information is surrounded by 16 byte sentinels.
+        let modinfo = if modinfo.len() >= 33 && modinfo[modinfo.len() - 17] == b'\n' {
+            &modinfo[16..modinfo.len() - 16]
+        } else {
+            &modinfo[..]
+        };
+
+        let mut info = GoBuildInfo::parse(&String::from_utf8_lossy(modinfo));
+        info.go_version = String::from_utf8_lossy(&version).into_owned();
+        Some(info)
+    }
+
+    /// Parse the module information, as written by the Go linker.
+    fn parse(modinfo: &str) -> GoBuildInfo {
+        let mut info = GoBuildInfo::default();
+        for line in modinfo.lines() {
+            let Some((key, value)) = line.split_once('\t') else {
+                continue;
+            };
+            match key {
+                "path" => info.path = value.to_string(),
+                "mod" => info.main = Some(GoModule::parse(value)),
+                "dep" => info.deps.push(GoModule::parse(value)),
+                "=>" => {
+                    let replaced = match info.deps.last_mut() {
+                        Some(dep) => Some(dep),
+                        None => info.main.as_mut(),
+                    };
+                    if let Some(module) = replaced {
+                        module.replace = Some(Box::new(GoModule::parse(value)));
+                    }
+                }
+                "build" => {
+                    if let Some((key, value)) = value.split_once('=') {
+                        info.settings.push((unquote(key), unquote(value)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        info
+    }
+}
+
+impl GoModule {
+    fn parse(value: &str) -> GoModule {
+        let mut fields = value.split('\t');
+        GoModule {
+            path: fields.next().unwrap_or_default().to_string(),
+            version: fields.next().unwrap_or_default().to_string(),
+            sum: fields.next().unwrap_or_default().to_string(),
+            replace: None,
+        }
+    }
+}
+
+/// Find the offset of the header of the build information in the file.
+///
+/// Without sections, the build information is searched for in the writable
+/// data segments. An external linker may place a relocation read-only segment
+/// first, so those segments are searched last.
+fn find_header(elf: &ReadElf<'_>) -> Option<u64> {
+    let relro: Vec<ProgramHeader> = elf
+        .segments_of_type(SegmentType::from(PT_GNU_RELRO))
+        .collect();
+    let mut data: Vec<ProgramHeader> = elf
+        .program_headers()
+        .filter(|ph| ph.is_loadable() && ph.flags.is_writable() && !ph.flags.is_executable())
+        .collect();
+    data.sort_by_key(|ph| relro.iter().any(|r| r.contains_vaddr(ph.virtual_address)));
+
+    for ph in data {
+        let Some(range) = ph.file_range() else {
+            continue;
+        };
+
+        // The header is aligned in memory.
+        let skip = ph.virtual_address.wrapping_neg() % BUILD_INFO_ALIGN;
+        let mut header = range.start.saturating_add(skip);
+        while header.saturating_add(BUILD_INFO_HEADER_SIZE) <= range.end {
+            if is_magic(elf, header)? {
+                return Some(header);
+            }
+            header += BUILD_INFO_ALIGN;
+        }
+    }
+    None
+}
+
+fn is_magic(elf: &ReadElf<'_>, offset: u64) -> Option<bool> {
+    let mut magic = [0; BUILD_INFO_MAGIC.len()];
+    elf.parser.read_into(offset, &mut magic)?;
+    Some(magic == *BUILD_INFO_MAGIC)
+}
+
+/// Read a string prefixed with its length as an unsigned LEB128 value, that
+/// is at most `max_len` bytes long.
+fn read_string(elf: &ReadElf<'_>, offset: &mut u64, max_len: u64) -> Option<Vec<u8>> {
+    let mut len = 0_u64;
+    let mut shift = 0;
+    loop {
+        let b = elf.parser.get_u8(*offset)?;
+        *offset += 1;
+        if shift > 63 {
+            return None;
+        }
+        len |= u64::from(b & 0x7F) << shift;
+        if b & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    if len > max_len {
+        return None;
+    }
+
+    let start = *offset;
+    *offset = start.checked_add(len)?;
+    elf.read_bytes(start..*offset)
+}
+
+/// Remove the quotes of a value in the build settings.
+///
+/// Go quotes keys and values with spaces, quotes or an `=`.
+fn unquote(s: &str) -> String {
+    match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(s) => s.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unquote, GoBuildInfo, GoModule};
+
+    #[test]
+    fn parse_modinfo() {
+        let info = GoBuildInfo::parse(
+            "path\texample.com/cmd/tool\n\
+             mod\texample.com\t(devel)\t\n\
+             dep\tgolang.org/x/sys\tv0.20.0\th1:abc=\n\
+             dep\texample.com/lib\tv1.0.0\n\
+             =>\t../lib\t(devel)\t\n\
+             build\t-ldflags=\"-s -w\"\n\
+             build\tGOOS=linux\n",
+        );
+        assert_eq!(info.path, "example.com/cmd/tool");
+        assert_eq!(info.main.as_ref().unwrap().version, "(devel)");
+        assert_eq!(info.deps.len(), 2);
+        assert_eq!(info.deps[0].sum, "h1:abc=");
+        assert_eq!(info.deps[0].replace, None);
+        assert_eq!(
+            info.deps[1].replace.as_deref(),
+            Some(&GoModule {
+                path: "../lib".to_string(),
+                version: "(devel)".to_string(),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            info.settings,
+            vec![
+                ("-ldflags".to_string(), "-s -w".to_string()),
+                ("GOOS".to_string(), "linux".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn unquote_value() {
+        assert_eq!(unquote("linux"), "linux");
+        assert_eq!(unquote("\"-X \\\"a=b\\\"\""), "-X \"a=b\"");
+        assert_eq!(unquote("\""), "\"");
+    }
+}
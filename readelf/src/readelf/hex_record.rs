@@ -0,0 +1,139 @@
+use crate::BinaryImage;
+use std::fmt::Write;
+
+impl BinaryImage {
+    /// Write the image in the Intel HEX format.
+    ///
+    /// Each data record has up to `record_size` bytes, and doesn't cross a
+    /// 64KiB boundary. Addresses above 64KiB use extended linear address
+    /// records. The start linear address record has the
+    /// [BinaryImage::entry], if it is not zero. Lines end with `\r\n`.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if `record_size` is zero, or the image ends
+    /// above 4GiB.
+    pub fn to_ihex(&self, record_size: u8) -> Option<String> {
+        let size = u64::try_from(self.data.len()).ok()?;
+        if record_size == 0 || self.base.checked_add(size)? > 1 << 32 {
+            return None;
+        }
+
+        let mut out = String::new();
+        let mut upper = 0;
+        let mut offset = 0;
+        while offset < self.data.len() {
+            let address = self.base + offset as u64;
+            if address >> 16 != upper {
+                upper = address >> 16;
+                ihex_record(&mut out, 0, 0x04, &(upper as u16).to_be_bytes());
+            }
+
+            // Split at the record size, and at the 64KiB boundary.
+            let len = usize::from(record_size)
+                .min(self.data.len() - offset)
+                .min(0x10000 - (address & 0xFFFF) as usize);
+            let chunk = &self.data[offset..offset + len];
+            ihex_record(&mut out, address as u16, 0x00, chunk);
+            offset += len;
+        }
+
+        if self.entry != 0 && self.entry <= u64::from(u32::MAX) {
+            ihex_record(&mut out, 0, 0x05, &(self.entry as u32).to_be_bytes());
+        }
+        ihex_record(&mut out, 0, 0x01, &[]);
+        Some(out)
+    }
+
+    /// Write the image in the Motorola S-record format.
+    ///
+    /// The image starts with an `S0` header record. The data records are
+    /// `S1`, `S2` or `S3`, with the smallest address size for the end of the
+    /// image, and have up to `record_size` bytes. They are followed by an
+    /// `S5` or `S6` record with the number of data records, and an `S9`, `S8`
+    /// or `S7` record with the [BinaryImage::entry]. Lines end with `\r\n`.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if `record_size` is zero, a record would be
+    /// larger than 255 bytes, or the image or the entry point is above 4GiB.
+    pub fn to_srec(&self, record_size: u8) -> Option<String> {
+        let size = u64::try_from(self.data.len()).ok()?;
+        let end = self.base.checked_add(size)?;
+        let address_size: usize = match end.max(self.entry.saturating_add(1)) {
+            0..=0x1_0000 => 2,
+            0x1_0001..=0x100_0000 => 3,
+            0x100_0001..=0x1_0000_0000 => 4,
+            _ => return None,
+        };
+        if record_size == 0 || usize::from(record_size) + address_size + 1 > 0xFF {
+            return None;
+        }
+
+        let mut out = String::new();
+        srec_record(&mut out, 0, 0, 2, &[]);
+
+        let mut count = 0_u64;
+        for (i, chunk) in self.data.chunks(usize::from(record_size)).enumerate() {
+            let address = self.base + (i * usize::from(record_size)) as u64;
+            srec_record(
+                &mut out,
+                address_size as u8 - 1,
+                address,
+                address_size,
+                chunk,
+            );
+            count += 1;
+        }
+
+        if count <= 0xFFFF {
+            srec_record(&mut out, 5, count, 2, &[]);
+        } else if count <= 0xFF_FFFF {
+            srec_record(&mut out, 6, count, 3, &[]);
+        }
+        srec_record(
+            &mut out,
+            11 - address_size as u8,
+            self.entry,
+            address_size,
+            &[],
+        );
+        Some(out)
+    }
+}
+
+/// Write a record of the Intel HEX format.
+fn ihex_record(out: &mut String, address: u16, record_type: u8, data: &[u8]) {
+    // The length is limited by the record size of a `u8`.
+    let mut record = vec![data.len() as u8];
+    record.extend_from_slice(&address.to_be_bytes());
+    record.push(record_type);
+    record.extend_from_slice(data);
+
+    let checksum = record.iter().fold(0_u8, |s, b| s.wrapping_add(*b));
+    record.push(checksum.wrapping_neg());
+
+    out.push(':');
+    for b in record {
+        let _ = write!(out, "{:02X}", b);
+    }
+    out.push_str("\r\n");
+}
+
+/// Write a record of the Motorola S-record format, with an address of
+/// `address_size` bytes.
+fn srec_record(out: &mut String, record_type: u8, address: u64, address_size: usize, data: &[u8]) {
+    // The length is checked to fit in a `u8` by the caller.
+    let mut record = vec![(address_size + data.len() + 1) as u8];
+    record.extend_from_slice(&address.to_be_bytes()[8 - address_size..]);
+    record.extend_from_slice(data);
+
+    let checksum = record.iter().fold(0_u8, |s, b| s.wrapping_add(*b));
+    record.push(!checksum);
+
+    let _ = write!(out, "S{}", record_type);
+    for b in record {
+        let _ = write!(out, "{:02X}", b);
+    }
+    out.push_str("\r\n");
+}
@@ -0,0 +1,106 @@
+use crate::ReadElf;
+use serde::Serialize;
+
+/// A value of the ELF file that has a numeric representation and a name.
+#[derive(Serialize)]
+struct Named<T> {
+    value: T,
+    name: String,
+}
+
+impl<T> Named<T> {
+    fn new<V>(value: V) -> Named<T>
+    where
+        V: Copy + ToString,
+        T: From<V>,
+    {
+        Named {
+            value: T::from(value),
+            name: value.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Header {
+    class: Named<u8>,
+    data: Named<u8>,
+    version: u32,
+    osabi: Named<u8>,
+    abi_version: u8,
+    exec_type: Named<u16>,
+    machine: Named<u16>,
+    entry: u64,
+    flags: u32,
+    file_header_size: u16,
+    program_header_offset: u64,
+    program_header_size: u16,
+    program_header_count: u16,
+    section_header_offset: u64,
+    section_header_size: u16,
+    section_header_count: u16,
+    string_section_index: u16,
+}
+
+#[derive(Serialize)]
+struct Segment {
+    index: usize,
+    segment_type: Named<u32>,
+    flags: Named<u32>,
+    file_offset: u64,
+    virtual_address: u64,
+    physical_address: u64,
+    file_size: u64,
+    memory_size: u64,
+    alignment: u64,
+}
+
+/// The structure of the document written by [ReadElf::to_json()].
+#[derive(Serialize)]
+pub(super) struct Document {
+    header: Header,
+    segments: Vec<Segment>,
+}
+
+impl Document {
+    pub(super) fn new(elf: &ReadElf<'_>) -> Document {
+        let raw = elf.raw_header();
+        let header = Header {
+            class: Named::new(elf.class),
+            data: Named::new(elf.data),
+            version: elf.version,
+            osabi: Named::new(elf.osabi),
+            abi_version: elf.abi_version,
+            exec_type: Named::new(elf.exec_type),
+            machine: Named::new(elf.machine),
+            entry: elf.entry,
+            flags: elf.flags,
+            file_header_size: raw.file_header_size,
+            program_header_offset: raw.program_header_offset,
+            program_header_size: raw.program_header_size,
+            program_header_count: raw.program_header_count,
+            section_header_offset: raw.section_header_offset,
+            section_header_size: raw.section_header_size,
+            section_header_count: raw.section_header_count,
+            string_section_index: raw.string_section_index,
+        };
+
+        let segments = elf
+            .program_headers()
+            .indexed()
+            .map(|(index, ph)| Segment {
+                index,
+                segment_type: Named::new(ph.segment_type),
+                flags: Named::new(ph.flags),
+                file_offset: ph.file_offset,
+                virtual_address: ph.virtual_address,
+                physical_address: ph.physical_address,
+                file_size: ph.file_size,
+                memory_size: ph.memory_size,
+                alignment: ph.alignment,
+            })
+            .collect();
+
+        Document { header, segments }
+    }
+}
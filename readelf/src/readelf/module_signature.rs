@@ -0,0 +1,84 @@
+use crate::{Endian, ReadElf};
+use std::ops::Range;
+
+/// The marker at the end of a signed Linux kernel module.
+const MODULE_SIG_STRING: &[u8] = b"~Module signature appended~\n";
+
+/// The size of `struct module_signature`, preceding the marker.
+const MODULE_SIG_INFO_SIZE: u64 = 12;
+
+/// The signature appended to a Linux kernel module.
+///
+/// A signed kernel module has the signer's name, the key identifier, the
+/// signature and an information block appended after the ELF file, followed
+/// by the marker `~Module signature appended~`. For signatures in the PKCS#7
+/// format, which is the only format used by current kernels, the signer and
+/// key identifier are inside the signature and are empty here.
+///
+/// Get an instance with [ReadElf::module_signature()].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModuleSignature {
+    /// The public key algorithm, `0` for PKCS#7.
+    pub algorithm: u8,
+
+    /// The digest algorithm, `0` for PKCS#7.
+    pub hash: u8,
+
+    /// The type of the key identifier, e.g. [ModuleSignature::PKEY_ID_PKCS7].
+    pub id_type: u8,
+
+    /// The name of the signer.
+    pub signer: Vec<u8>,
+
+    /// The identifier of the key.
+    pub key_id: Vec<u8>,
+
+    /// The offsets of the signature in the file.
+    pub signature: Range<u64>,
+
+    /// The size of the module, without the signature.
+    pub module_size: u64,
+}
+
+impl ModuleSignature {
+    /// The key identifier is for PGP.
+    pub const PKEY_ID_PGP: u8 = 0;
+
+    /// The key identifier is for an X.509 certificate.
+    pub const PKEY_ID_X509: u8 = 1;
+
+    /// The signature is a PKCS#7 message.
+    pub const PKEY_ID_PKCS7: u8 = 2;
+
+    pub(super) fn read(elf: &ReadElf<'_>) -> Option<ModuleSignature> {
+        let len = elf.parser.len()?;
+        let marker = len.checked_sub(MODULE_SIG_STRING.len() as u64)?;
+        let mut marker_bytes = [0; MODULE_SIG_STRING.len()];
+        elf.parser.read_into(marker, &mut marker_bytes)?;
+        if marker_bytes != MODULE_SIG_STRING {
+            return None;
+        }
+
+        let info = marker.checked_sub(MODULE_SIG_INFO_SIZE)?;
+        let algorithm = elf.parser.get_u8(info)?;
+        let hash = elf.parser.get_u8(info + 1)?;
+        let id_type = elf.parser.get_u8(info + 2)?;
+        let signer_len = elf.parser.get_u8(info + 3)?;
+        let key_id_len = elf.parser.get_u8(info + 4)?;
+        let sig_len = elf.parser.get_u32(info + 8, Endian::Big)?;
+
+        let signature_start = info.checked_sub(u64::from(sig_len))?;
+        let key_id_start = signature_start.checked_sub(u64::from(key_id_len))?;
+        let signer_start = key_id_start.checked_sub(u64::from(signer_len))?;
+
+        Some(ModuleSignature {
+            algorithm,
+            hash,
+            id_type,
+            signer: elf.read_bytes(signer_start..key_id_start)?,
+            key_id: elf.read_bytes(key_id_start..signature_start)?,
+            signature: signature_start..info,
+            module_size: signer_start,
+        })
+    }
+}
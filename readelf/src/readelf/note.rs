@@ -0,0 +1,87 @@
+use crate::{ProgramHeader, ReadElf, SegmentType};
+
+/// A note from a [SegmentType::Note] segment.
+///
+/// Notes give information about the ELF file, such as the build ID or the ABI
+/// of the operating system. The meaning of the type depends on the owner given
+/// by the name. Get the notes with [ReadElf::notes()].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Note {
+    /// The owner of the note, e.g. `GNU`, without the terminating NUL.
+    pub name: String,
+
+    /// The type of the note, interpreted depending on the owner.
+    pub note_type: u32,
+
+    /// The contents of the note.
+    pub desc: Vec<u8>,
+}
+
+impl Note {
+    pub(super) fn read_all(elf: &ReadElf<'_>) -> Vec<Note> {
+        let mut notes = Vec::new();
+        for ph in elf.segments_of_type(SegmentType::Note) {
+            Note::read_segment(elf, &ph, &mut notes);
+        }
+        notes
+    }
+
+    /// Read the notes of a segment, stopping at the first note that can't be
+    /// read.
+    ///
+    /// Segments larger than [crate::ReadOptions::max_allocation] are skipped,
+    /// as all their notes are read.
+    fn read_segment(elf: &ReadElf<'_>, ph: &ProgramHeader, notes: &mut Vec<Note>) {
+        if ph.file_size > elf.options.max_allocation {
+            return;
+        }
+        let Some(range) = ph.file_range() else {
+            return;
+        };
+
+        // Notes are aligned to 4 bytes, or to 8 bytes for the GNU property
+        // notes in segments aligned to 8 bytes.
+        let align = if ph.alignment == 8 { 8 } else { 4 };
+
+        let mut offset = range.start;
+        while offset < range.end {
+            let Some((note, next)) = Note::read(elf, offset, range.end, align) else {
+                return;
+            };
+            notes.push(note);
+            offset = next;
+        }
+    }
+
+    /// Read a single note at the offset, returning the offset of the next note.
+    fn read(elf: &ReadElf<'_>, offset: u64, end: u64, align: u64) -> Option<(Note, u64)> {
+        let name_size = u64::from(elf.parser.get_u32(offset, elf.data)?);
+        let desc_size = u64::from(elf.parser.get_u32(offset.checked_add(4)?, elf.data)?);
+        let note_type = elf.parser.get_u32(offset.checked_add(8)?, elf.data)?;
+        if name_size > elf.options.max_allocation || desc_size > elf.options.max_allocation {
+            return None;
+        }
+
+        let name_offset = offset.checked_add(12)?;
+        let desc_offset = align_up(name_offset.checked_add(name_size)?, align)?;
+        let desc_end = desc_offset.checked_add(desc_size)?;
+        if desc_end > end {
+            return None;
+        }
+
+        let mut name = elf.read_bytes(name_offset..name_offset + name_size)?;
+        while name.last() == Some(&0) {
+            name.pop();
+        }
+        let note = Note {
+            name: String::from_utf8_lossy(&name).into_owned(),
+            note_type,
+            desc: elf.read_bytes(desc_offset..desc_end)?,
+        };
+        Some((note, align_up(desc_end, align)?))
+    }
+}
+
+fn align_up(offset: u64, align: u64) -> Option<u64> {
+    Some(offset.checked_add(align - 1)? & !(align - 1))
+}
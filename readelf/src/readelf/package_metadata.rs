@@ -0,0 +1,97 @@
+use crate::{Note, ReadElf};
+
+/// The type of the note `NT_FDO_PACKAGING_METADATA`.
+const NT_FDO_PACKAGING_METADATA: u32 = 0xCAFE1A7E;
+
+/// The package that an ELF file belongs to.
+///
+/// This is the JSON document of the `NT_FDO_PACKAGING_METADATA` note, as
+/// described by the [systemd](https://systemd.io/ELF_PACKAGE_METADATA/)
+/// specification, written by distributions into the `.note.package` section.
+/// Get an instance with [ReadElf::package_metadata()].
+///
+/// The other fields are parsed from the JSON document, and only exist with
+/// the feature `serde`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PackageMetadata {
+    /// The JSON document of the note.
+    pub json: String,
+
+    /// The type of the package, e.g. `rpm` or `deb`, from the key `type`.
+    #[cfg(feature = "serde")]
+    pub package_type: Option<String>,
+
+    /// The operating system, e.g. `fedora`, from the key `os`.
+    #[cfg(feature = "serde")]
+    pub os: Option<String>,
+
+    /// The version of the operating system, from the key `osVersion`.
+    #[cfg(feature = "serde")]
+    pub os_version: Option<String>,
+
+    /// The name of the package, from the key `name`.
+    #[cfg(feature = "serde")]
+    pub name: Option<String>,
+
+    /// The version of the package, from the key `version`.
+    #[cfg(feature = "serde")]
+    pub version: Option<String>,
+
+    /// The architecture of the package, from the key `architecture`.
+    #[cfg(feature = "serde")]
+    pub architecture: Option<String>,
+
+    /// The URL of the debuginfod server, from the key `debugInfoUrl`.
+    #[cfg(feature = "serde")]
+    pub debug_info_url: Option<String>,
+}
+
+impl PackageMetadata {
+    pub(super) fn read(elf: &ReadElf<'_>) -> Option<PackageMetadata> {
+        let note = elf
+            .notes()
+            .into_iter()
+            .find(|n| n.name == "FDO" && n.note_type == NT_FDO_PACKAGING_METADATA)?;
+        Some(PackageMetadata::parse(&note))
+    }
+
+    fn parse(note: &Note) -> PackageMetadata {
+        let json = note.desc.split(|b| *b == 0).next().unwrap_or_default();
+        let json = String::from_utf8_lossy(json).into_owned();
+        PackageMetadata::from_json(json)
+    }
+
+    #[cfg(feature = "serde")]
+    fn from_json(json: String) -> PackageMetadata {
+        /// The keys of the specification, which only allows string values.
+        #[derive(Default, serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Fields {
+            #[serde(rename = "type")]
+            package_type: Option<String>,
+            os: Option<String>,
+            os_version: Option<String>,
+            name: Option<String>,
+            version: Option<String>,
+            architecture: Option<String>,
+            debug_info_url: Option<String>,
+        }
+
+        let fields: Fields = serde_json::from_str(&json).unwrap_or_default();
+        PackageMetadata {
+            json,
+            package_type: fields.package_type,
+            os: fields.os,
+            os_version: fields.os_version,
+            name: fields.name,
+            version: fields.version,
+            architecture: fields.architecture,
+            debug_info_url: fields.debug_info_url,
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn from_json(json: String) -> PackageMetadata {
+        PackageMetadata { json }
+    }
+}
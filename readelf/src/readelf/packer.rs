@@ -0,0 +1,230 @@
+use crate::{ProgramHeader, ReadElf};
+
+/// The magic of UPX in the `l_info` and `p_info` headers of a packed file.
+const UPX_MAGIC: &[u8; 4] = b"UPX!";
+
+/// The entropy in bits per byte above which a segment is considered to be
+/// compressed or encrypted.
+const HIGH_ENTROPY: f64 = 7.2;
+
+/// The minimum size of a segment to calculate the entropy for, as small
+/// segments can't have a high entropy.
+const MIN_ENTROPY_SIZE: u64 = 512;
+
+/// The minimum memory size of a segment that is expanded at runtime.
+const MIN_EXPANDED_SIZE: u64 = 0x10000;
+
+/// A sign that an ELF file is packed, found by [ReadElf::packer_report()].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PackerIndicator {
+    /// The magic `UPX!` of the UPX packer, at the offset in the file.
+    UpxMagic(u64),
+
+    /// The file has no section headers, which are removed by most packers.
+    NoSections,
+
+    /// The loadable segment with the index is both writable and executable.
+    WritableExecutable(usize),
+
+    /// The loadable segment with the index has much more memory than contents
+    /// in the file, where the unpacked program is written to at runtime.
+    ExpandedSegment(usize),
+
+    /// The entry point is not in an executable segment, or is in a writable
+    /// segment.
+    SuspiciousEntry,
+
+    /// The loadable segment with the index has the entropy in bits per byte,
+    /// typical for compressed or encrypted contents.
+    HighEntropy(usize, f64),
+}
+
+impl PackerIndicator {
+    /// The weight of the indicator for [PackerReport::confidence], in
+    /// percent.
+    pub fn weight(&self) -> u8 {
+        match self {
+            PackerIndicator::UpxMagic(_) => 50,
+            PackerIndicator::NoSections => 10,
+            PackerIndicator::WritableExecutable(_) => 20,
+            PackerIndicator::ExpandedSegment(_) => 15,
+            PackerIndicator::SuspiciousEntry => 20,
+            PackerIndicator::HighEntropy(..) => 25,
+        }
+    }
+}
+
+/// The result of the heuristics to detect a packed ELF file, such as one
+/// compressed with UPX.
+///
+/// The heuristics only use the ELF header and the loadable segments, so a
+/// file can have some of the indicators without being packed, e.g. a file
+/// with compressed resources has a high entropy. A packer that was modified
+/// to remove its magic is only found by the other indicators. Get an
+/// instance with [ReadElf::packer_report()].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackerReport {
+    /// The indicators that were found, in the order of the checks.
+    pub indicators: Vec<PackerIndicator>,
+
+    /// The confidence that the file is packed, from 0 to 100 percent.
+    ///
+    /// This is the sum of the weights of the indicators, limited to 100.
+    pub confidence: u8,
+}
+
+impl PackerReport {
+    pub(super) fn new(elf: &ReadElf<'_>) -> PackerReport {
+        let mut indicators = Vec::new();
+        let mut upx = None;
+        let mut segments = Vec::new();
+        for (index, ph) in elf.program_headers().enumerate() {
+            if !ph.is_loadable() {
+                continue;
+            }
+            let scan = scan_segment(elf, &ph);
+            upx = upx.or(scan.and_then(|s| s.upx));
+            segments.push((index, ph, scan.and_then(|s| s.entropy)));
+        }
+
+        if let Some(offset) = upx {
+            indicators.push(PackerIndicator::UpxMagic(offset));
+        }
+        if matches!(elf.section_count(), None | Some(0)) {
+            indicators.push(PackerIndicator::NoSections);
+        }
+        for (index, ph, _) in &segments {
+            if ph.flags.is_writable() && ph.flags.is_executable() {
+                indicators.push(PackerIndicator::WritableExecutable(*index));
+            }
+        }
+        for (index, ph, _) in &segments {
+            if is_expanded(ph) {
+                indicators.push(PackerIndicator::ExpandedSegment(*index));
+            }
+        }
+        if elf.entry != 0 {
+            let writable = elf.entry_segment().is_some_and(|ph| ph.flags.is_writable());
+            if writable || !elf.is_entry_executable() {
+                indicators.push(PackerIndicator::SuspiciousEntry);
+            }
+        }
+        for (index, _, entropy) in &segments {
+            if let Some(entropy) = entropy.filter(|e| *e >= HIGH_ENTROPY) {
+                indicators.push(PackerIndicator::HighEntropy(*index, entropy));
+            }
+        }
+
+        let confidence = indicators
+            .iter()
+            .map(|i| u32::from(i.weight()))
+            .sum::<u32>()
+            .min(100) as u8;
+        PackerReport {
+            indicators,
+            confidence,
+        }
+    }
+
+    /// Check if the file is likely packed, with a confidence of at least 50
+    /// percent.
+    pub fn is_packed(&self) -> bool {
+        self.confidence >= 50
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SegmentScan {
+    /// The offset of the first UPX magic in the file.
+    upx: Option<u64>,
+
+    /// The entropy of the contents in bits per byte.
+    entropy: Option<f64>,
+}
+
+/// Read the contents of the segment in the file once, searching for the UPX
+/// magic and counting the bytes for the entropy.
+///
+/// # Returns
+///
+/// Returns [Option::None] if the segment is larger than
+/// [crate::ReadOptions::max_allocation], or can't be read.
+fn scan_segment(elf: &ReadElf<'_>, ph: &ProgramHeader) -> Option<SegmentScan> {
+    if ph.file_size > elf.options.max_allocation {
+        return None;
+    }
+    let range = ph.file_range()?;
+
+    let mut counts = [0_u64; 256];
+    let mut total = 0_u64;
+    let mut window = [0_u8; 4];
+    let mut upx = None;
+    elf.read_chunks(range.clone(), |offset, chunk| {
+        for (i, b) in chunk.iter().enumerate() {
+            counts[usize::from(*b)] += 1;
+            window = [window[1], window[2], window[3], *b];
+            let end = offset + i as u64;
+            if upx.is_none() && window == *UPX_MAGIC && end >= range.start + 3 {
+                upx = Some(end - 3);
+            }
+        }
+        total += chunk.len() as u64;
+    })?;
+
+    let entropy = match total >= MIN_ENTROPY_SIZE {
+        true => Some(entropy(&counts, total)),
+        false => None,
+    };
+    Some(SegmentScan { upx, entropy })
+}
+
+/// Check if the memory of the segment is expanded at runtime.
+///
+/// The bss of a linked program is usually in the writable data segment, which
+/// has contents in the file, so a large segment without contents, or an
+/// executable segment much larger than its contents, is unusual.
+fn is_expanded(ph: &ProgramHeader) -> bool {
+    if ph.memory_size < MIN_EXPANDED_SIZE {
+        return false;
+    }
+    match ph.file_size {
+        0 => true,
+        size => ph.flags.is_executable() && ph.memory_size / 4 >= size,
+    }
+}
+
+/// Calculate the Shannon entropy in bits per byte from the number of
+/// occurrences of each byte value.
+fn entropy(counts: &[u64; 256], total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let total = total as f64;
+    counts
+        .iter()
+        .filter(|c| **c > 0)
+        .map(|c| {
+            let p = *c as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::entropy;
+
+    #[test]
+    fn entropy_bits() {
+        let mut counts = [0; 256];
+        counts[0] = 1024;
+        assert_eq!(entropy(&counts, 1024), 0.0);
+
+        counts[1] = 1024;
+        assert_eq!(entropy(&counts, 2048), 1.0);
+
+        let counts = [4; 256];
+        assert_eq!(entropy(&counts, 1024), 8.0);
+        assert_eq!(entropy(&[0; 256], 0), 0.0);
+    }
+}
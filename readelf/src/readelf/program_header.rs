@@ -1,7 +1,8 @@
-use crate::{Class, ReadElf, SegmentFlags, SegmentType};
+use crate::{Class, ParseError, ReadElf, SegmentFlags, SegmentType};
+use std::ops::Range;
 
 /// Describes a segment on how an OS creates a process image.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct ProgramHeader {
     /// Identifies the type of the segment.
     pub segment_type: SegmentType,
@@ -32,20 +33,36 @@ pub struct ProgramHeader {
 }
 
 impl ProgramHeader {
-    pub(super) fn new<'elf>(elf: &'elf ReadElf<'elf>, index: u16) -> Option<ProgramHeader> {
+    pub(super) fn new<'elf>(elf: &'elf ReadElf<'elf>, index: usize) -> Option<ProgramHeader> {
+        ProgramHeader::try_new(elf, index).ok()
+    }
+
+    pub(super) fn try_new<'elf>(
+        elf: &'elf ReadElf<'elf>,
+        index: usize,
+    ) -> Result<ProgramHeader, ParseError> {
+        let min_phentsize = match elf.class {
+            Class::Elf32 => 32_u16,
+            Class::Elf64 => 56_u16,
+        };
+        if elf.program_header_size < min_phentsize {
+            return Err(ParseError::EntrySize(elf.program_header_size));
+        }
+
         // Ensure that the base offset of the segment doesn't overflow. The
         // `BinParser` will always check that the offsets are within the file.
-        // The `ProgramHeaders`` class has already checked the validity of the
-        // `program_header_size` field meets the minimum required.
-        //
-        // Note, here `offset` can't overflow. Multiplication of two 16-bit
-        // values results in a 32-bit value.
-        let offset = (index as u64) * (elf.program_header_size as u64);
-        let base = elf.program_header_offset.checked_add(offset)?;
+        let base = (index as u64)
+            .checked_mul(elf.program_header_size as u64)
+            .and_then(|offset| elf.program_header_offset.checked_add(offset))
+            .ok_or(ParseError::Overflow(index))?;
         if base > u64::MAX - elf.program_header_size as u64 {
-            return None;
+            return Err(ParseError::Overflow(index));
         }
 
+        ProgramHeader::read(elf, base).ok_or(ParseError::Truncated(index))
+    }
+
+    fn read<'elf>(elf: &'elf ReadElf<'elf>, base: u64) -> Option<ProgramHeader> {
         match elf.class {
             Class::Elf32 => Some(ProgramHeader {
                 segment_type: SegmentType::from(elf.parser.get_u32(base, elf.data)?),
@@ -70,6 +87,97 @@ impl ProgramHeader {
         }
     }
 
+    /// Check if the segment is of the type given.
+    ///
+    /// The types are compared by their value, so that a [SegmentType::Unknown]
+    /// matches a known variant with the same value.
+    pub(crate) fn is_type(&self, segment_type: SegmentType) -> bool {
+        u32::from(self.segment_type) == u32::from(segment_type)
+    }
+
+    /// Check if the segment is loaded into memory, [SegmentType::Load].
+    pub fn is_loadable(&self) -> bool {
+        self.is_type(SegmentType::Load)
+    }
+
+    /// Check if the segment has the dynamic linking information,
+    /// [SegmentType::Dynamic].
+    pub fn is_dynamic(&self) -> bool {
+        self.is_type(SegmentType::Dynamic)
+    }
+
+    /// Check if the segment has the path of the program interpreter,
+    /// [SegmentType::Interpreter].
+    pub fn is_interpreter(&self) -> bool {
+        self.is_type(SegmentType::Interpreter)
+    }
+
+    /// Check if the segment has auxiliary information, [SegmentType::Note].
+    pub fn is_note(&self) -> bool {
+        self.is_type(SegmentType::Note)
+    }
+
+    /// Check if the segment is the thread-local storage template,
+    /// [SegmentType::ThreadLocalStorage].
+    pub fn is_tls(&self) -> bool {
+        self.is_type(SegmentType::ThreadLocalStorage)
+    }
+
+    /// Get the range of bytes of the segment in the file.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the end of the segment overflows.
+    pub fn file_range(&self) -> Option<Range<u64>> {
+        let end = self.file_offset.checked_add(self.file_size)?;
+        Some(self.file_offset..end)
+    }
+
+    /// Get the first virtual address after the segment in memory.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the end of the segment overflows.
+    pub fn end_vaddr(&self) -> Option<u64> {
+        self.virtual_address.checked_add(self.memory_size)
+    }
+
+    /// Get the range of virtual addresses of the segment in memory.
+    ///
+    /// # Returns
+    ///
+    /// Returns [Option::None] if the end of the segment overflows.
+    pub fn vaddr_range(&self) -> Option<Range<u64>> {
+        Some(self.virtual_address..self.end_vaddr()?)
+    }
+
+    /// Check if the virtual address is in the segment in memory.
+    ///
+    /// A segment that overflows contains no addresses.
+    pub fn contains_vaddr(&self, addr: u64) -> bool {
+        self.vaddr_range().is_some_and(|r| r.contains(&addr))
+    }
+
+    /// Check if the offset in the file is in the segment.
+    ///
+    /// A segment that overflows contains no offsets.
+    pub fn contains_offset(&self, offset: u64) -> bool {
+        self.file_range().is_some_and(|r| r.contains(&offset))
+    }
+
+    /// Get a copy of the program header with the virtual address moved by the
+    /// load bias.
+    ///
+    /// Use [ReadElf::load_bias()] to get the load bias of a loaded ELF file.
+    /// The arithmetic wraps, as the load bias may be negative. The physical
+    /// address is not changed.
+    pub fn rebased(&self, load_bias: u64) -> ProgramHeader {
+        ProgramHeader {
+            virtual_address: self.virtual_address.wrapping_add(load_bias),
+            ..*self
+        }
+    }
+
     /// Check if the alignment is correct for a loadable segment.
     ///
     /// Loadable process segments must have congruent values for
@@ -89,4 +197,21 @@ impl ProgramHeader {
             || self.alignment.is_power_of_two()
                 && (self.virtual_address.wrapping_sub(self.file_offset) % self.alignment) == 0
     }
+
+    /// Check if a loadable segment can be mapped with pages of `page_size`.
+    ///
+    /// The [ProgramHeader::alignment] must be at least the page size, so that
+    /// a loader doesn't place segments with different permissions in the same
+    /// page, and [ProgramHeader::virtual_address] must equal
+    /// [ProgramHeader::file_offset] modulo the page size. A `page_size` that
+    /// is not a power of two is never satisfied.
+    ///
+    /// Use [crate::Machine::max_page_size()] to check that a segment works on
+    /// all kernels of the architecture, e.g. with 16KiB or 64KiB pages on
+    /// [crate::Machine::AARCH64].
+    pub fn is_page_aligned(&self, page_size: u64) -> bool {
+        page_size.is_power_of_two()
+            && self.alignment >= page_size
+            && (self.virtual_address.wrapping_sub(self.file_offset) % page_size) == 0
+    }
 }
@@ -1,51 +1,68 @@
-use crate::{Class, ProgramHeader, ReadElf, SegmentFlags, SegmentType};
+use crate::{ParseError, ProgramHeader, ReadElf};
 
 /// An iterator for all program headers in the ELF file.
-#[derive(Debug)]
+///
+/// Iterating over a reference iterates over a copy of the iterator, leaving the
+/// original unchanged.
+#[derive(Clone, Debug)]
 pub struct ProgramHeaders<'elf> {
     elf: &'elf ReadElf<'elf>,
-    index: u16,
+    count: usize,
+    index: usize,
+    end: usize,
 }
 
 impl<'elf> ProgramHeaders<'elf> {
     /// Create a new iterator for ELF segments.
     pub(super) fn new(elf: &'elf ReadElf<'elf>) -> ProgramHeaders<'elf> {
-        let min_phentsize = match elf.class {
-            Class::Elf32 => 32_u16,
-            Class::Elf64 => 56_u16,
-        };
-
-        let i = if elf.program_header_size < min_phentsize {
-            // Don't iterate, by indicating we're at the end.
-            elf.program_header_count
-        } else {
-            0
-        };
-
-        ProgramHeaders { elf, index: i }
+        // The size of the entries `e_phentsize` is checked when reading each
+        // program header. If the number of entries can't be read, the table
+        // is empty, as for ReadElf::program_header_range().
+        let count = elf
+            .segment_count()
+            .and_then(|c| usize::try_from(c).ok())
+            .unwrap_or_default();
+        ProgramHeaders {
+            elf,
+            count,
+            index: 0,
+            end: count,
+        }
     }
 
     /// Get the expected number of program header segments.
     ///
-    /// This is always the value of `e_phnum`, regardless if there that many
-    /// readable segments or not.
+    /// This is always the number of entries in the program header table,
+    /// `e_phnum`, or `sh_info` of the first section header if `e_phnum` is
+    /// `PN_XNUM`. It doesn't depend on if there are that many readable
+    /// segments or not, or how far the iterator has advanced.
     pub fn len(&self) -> usize {
-        self.elf.program_header_count as usize
+        self.count
     }
 
     /// Check if there are no segments in the ELF file.
     ///
-    /// This is always checked against `e_phnum`, regardless if there are that
-    /// many readable segments or not.
+    /// This is always checked against the number of entries given by
+    /// [ProgramHeaders::len()], regardless if there are that many readable
+    /// segments or not.
     pub fn is_empty(&self) -> bool {
-        self.elf.program_header_count == 0
+        self.count == 0
+    }
+
+    /// Get the number of program headers not yet iterated.
+    ///
+    /// This is an upper bound, as the iterator stops early on the first
+    /// program header that can't be read.
+    pub fn remaining(&self) -> usize {
+        self.end - self.index
     }
 
     /// Get the [ProgramHeader] at the specified index.
     ///
     /// Index directly into the ELF file to get the associated [ProgramHeader].
     /// The value of `index` must be in the range of 0 to
-    /// [ProgramHeaders::len()]. If it isn't, then [Option::None] is returned.
+    /// [ProgramHeaders::len()], independent of how far the iterator has
+    /// advanced. If it isn't, then [Option::None] is returned.
     /// If the index is in the range, a value of [Option::None] may still be
     /// returned in case there is a problem with the file (corruption, or
     /// truncated).
@@ -53,37 +70,175 @@ impl<'elf> ProgramHeaders<'elf> {
     /// Note that the `std::ops::Index` trait is not implemented, as the
     /// [ProgramHeader] is created lazily from the ELF file.
     pub fn index(&self, index: usize) -> Option<ProgramHeader> {
-        if index >= self.elf.program_header_count as usize {
+        if index >= self.count {
             None
         } else {
-            // Can't truncate, because it must be smaller than
-            // `program_header_count`.
-            ProgramHeader::new(self.elf, index as u16)
+            ProgramHeader::new(self.elf, index)
         }
     }
-}
 
-impl<'elf> Iterator for ProgramHeaders<'elf> {
-    type Item = ProgramHeader;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index == self.elf.program_header_count {
+    /// Get the next [ProgramHeader], or the reason why it can't be read.
+    ///
+    /// The [Iterator] implementation stops silently on the first program
+    /// header that can't be read, which can't be distinguished from the end of
+    /// the program header table. This method returns the [ParseError] instead,
+    /// after which iteration ends and [Option::None] is returned.
+    pub fn try_next(&mut self) -> Option<Result<ProgramHeader, ParseError>> {
+        if self.index == self.end {
             return None;
         }
 
-        let segment = ProgramHeader::new(self.elf, self.index);
+        let segment = ProgramHeader::try_new(self.elf, self.index);
         match segment {
-            Some(_) => {
+            Ok(_) => {
                 self.index += 1;
             }
-            None => {
-                self.index = self.elf.program_header_count;
+            Err(_) => {
+                self.index = self.end;
+            }
+        };
+        Some(segment)
+    }
+
+    /// Get the last [ProgramHeader] remaining, or the reason why it can't be
+    /// read.
+    ///
+    /// This is the same as [ProgramHeaders::try_next()], but iterating from
+    /// the end of the program header table.
+    pub fn try_next_back(&mut self) -> Option<Result<ProgramHeader, ParseError>> {
+        if self.index == self.end {
+            return None;
+        }
+
+        let segment = ProgramHeader::try_new(self.elf, self.end - 1);
+        match segment {
+            Ok(_) => {
+                self.end -= 1;
+            }
+            Err(_) => {
+                self.end = self.index;
             }
         };
-        segment
+        Some(segment)
+    }
+
+    /// Convert to an iterator that also returns the index of each program
+    /// header in the program header table.
+    ///
+    /// Unlike [Iterator::enumerate()], the index is correct also when
+    /// iterating from the back, or after the iterator has been advanced.
+    pub fn indexed(self) -> ProgramHeadersIndexed<'elf> {
+        ProgramHeadersIndexed { headers: self }
+    }
+
+    /// Convert to an iterator that returns the reason when a program header
+    /// can't be read.
+    ///
+    /// See [ProgramHeaders::try_next()].
+    pub fn iter_checked(self) -> ProgramHeadersChecked<'elf> {
+        ProgramHeadersChecked { headers: self }
+    }
+}
+
+impl<'elf> Iterator for ProgramHeaders<'elf> {
+    type Item = ProgramHeader;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next()?.ok()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.elf.program_header_count as usize))
+        // A corrupted file stops early, returning less elements.
+        (0, Some(self.remaining()))
+    }
+}
+
+impl<'elf> DoubleEndedIterator for ProgramHeaders<'elf> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.try_next_back()?.ok()
+    }
+}
+
+impl<'elf> IntoIterator for &ProgramHeaders<'elf> {
+    type Item = ProgramHeader;
+    type IntoIter = ProgramHeaders<'elf>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.clone()
+    }
+}
+
+/// An iterator for all program headers in the ELF file, that returns the
+/// reason why a program header can't be read.
+///
+/// Unlike [ProgramHeaders::try_next()], iteration doesn't end on an error.
+/// Each entry of the program header table is read on its own, so that there
+/// is exactly one item for each entry, [ProgramHeaders::len()] items in
+/// total.
+///
+/// Get an instance with [ProgramHeaders::iter_checked()].
+#[derive(Debug)]
+pub struct ProgramHeadersChecked<'elf> {
+    headers: ProgramHeaders<'elf>,
+}
+
+impl<'elf> Iterator for ProgramHeadersChecked<'elf> {
+    type Item = Result<ProgramHeader, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let headers = &mut self.headers;
+        if headers.index == headers.end {
+            return None;
+        }
+        headers.index += 1;
+        Some(ProgramHeader::try_new(headers.elf, headers.index - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.headers.remaining();
+        (len, Some(len))
+    }
+}
+
+impl<'elf> DoubleEndedIterator for ProgramHeadersChecked<'elf> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let headers = &mut self.headers;
+        if headers.index == headers.end {
+            return None;
+        }
+        headers.end -= 1;
+        Some(ProgramHeader::try_new(headers.elf, headers.end))
+    }
+}
+
+impl<'elf> ExactSizeIterator for ProgramHeadersChecked<'elf> {}
+
+/// An iterator for all program headers in the ELF file, together with their
+/// index in the program header table.
+///
+/// Get an instance with [ProgramHeaders::indexed()].
+#[derive(Clone, Debug)]
+pub struct ProgramHeadersIndexed<'elf> {
+    headers: ProgramHeaders<'elf>,
+}
+
+impl<'elf> Iterator for ProgramHeadersIndexed<'elf> {
+    type Item = (usize, ProgramHeader);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.headers.index;
+        self.headers.next().map(|ph| (index, ph))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.headers.size_hint()
+    }
+}
+
+impl<'elf> DoubleEndedIterator for ProgramHeadersIndexed<'elf> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // On success, `end` is the index of the program header returned.
+        let ph = self.headers.next_back()?;
+        Some((self.headers.end, ph))
     }
 }
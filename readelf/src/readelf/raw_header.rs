@@ -0,0 +1,67 @@
+use crate::ReadElf;
+
+/// The fields of the ELF header describing the layout of the ELF file.
+///
+/// These are the raw values as found in the ELF header, they're not checked
+/// against the contents of the file.
+///
+/// Get an instance with [ReadElf::raw_header()].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RawHeader {
+    /// Points to the start of the program header table.
+    ///
+    /// This value represents `e_phoff`.
+    pub program_header_offset: u64,
+
+    /// Points to the start of the section header table.
+    ///
+    /// This value represents `e_shoff`.
+    pub section_header_offset: u64,
+
+    /// Contains the size of this header, normally 64 Bytes for 64-bit and 52
+    /// Bytes for 32-bit format.
+    ///
+    /// This value represents `e_ehsize`.
+    pub file_header_size: u16,
+
+    /// Contains the size of a program header table entry.
+    ///
+    /// This value represents `e_phentsize`.
+    pub program_header_size: u16,
+
+    /// Contains the number of entries in the program header table.
+    ///
+    /// This value represents `e_phnum`.
+    pub program_header_count: u16,
+
+    /// Contains the size of a section header table entry.
+    ///
+    /// This value represents `e_shentsize`.
+    pub section_header_size: u16,
+
+    /// Contains the number of entries in the section header table.
+    ///
+    /// This value represents `e_shnum`.
+    pub section_header_count: u16,
+
+    /// Contains index of the section header table entry that contains the
+    /// section names.
+    ///
+    /// This value represents `e_shstrndx`.
+    pub string_section_index: u16,
+}
+
+impl RawHeader {
+    pub(super) fn new(elf: &ReadElf<'_>) -> RawHeader {
+        RawHeader {
+            program_header_offset: elf.program_header_offset,
+            section_header_offset: elf.section_header_offset,
+            file_header_size: elf.file_header_size,
+            program_header_size: elf.program_header_size,
+            program_header_count: elf.program_header_count,
+            section_header_size: elf.section_header_size,
+            section_header_count: elf.section_header_count,
+            string_section_index: elf.string_section_index,
+        }
+    }
+}
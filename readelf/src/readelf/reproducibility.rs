@@ -0,0 +1,104 @@
+use super::strings::read_only_strings;
+use crate::ReadElf;
+
+/// The minimum length of a string to be checked.
+const MIN_STRING_LEN: usize = 8;
+
+/// Directories that absolute paths of a build usually start with.
+const BUILD_DIRS: &[&str] = &[
+    "/home/",
+    "/Users/",
+    "/root/",
+    "/tmp/",
+    "/var/tmp/",
+    "/build/",
+    "/builddir/",
+];
+
+/// The month names as written by `__DATE__`.
+const MONTHS: &[&str] = &[
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The sources of non-determinism found in an ELF file.
+///
+/// Only the strings in the loadable segments that are not writable are
+/// checked, which contain the read-only data (`.rodata`). As sections are not
+/// read, strings only in the debug information or the symbol table are not
+/// found. Get an instance with [ReadElf::reproducibility_report()].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReproducibilityReport {
+    /// The build ID from the `NT_GNU_BUILD_ID` note, see
+    /// [ReadElf::build_id()].
+    pub build_id: Option<Vec<u8>>,
+
+    /// Strings with an absolute path in a build directory, e.g. `/home/`, with
+    /// their offset in the file.
+    pub build_paths: Vec<(u64, String)>,
+
+    /// Strings with a date or time in the format of the C macros `__DATE__`
+    /// or `__TIME__`, with their offset in the file.
+    pub timestamps: Vec<(u64, String)>,
+}
+
+impl ReproducibilityReport {
+    pub(super) fn new(elf: &ReadElf<'_>) -> ReproducibilityReport {
+        let mut report = ReproducibilityReport {
+            build_id: elf.build_id(),
+            ..Default::default()
+        };
+
+        for (offset, s) in read_only_strings(elf, MIN_STRING_LEN) {
+            if BUILD_DIRS.iter().any(|d| s.contains(d)) {
+                report.build_paths.push((offset, s.clone()));
+            }
+            if has_timestamp(&s) {
+                report.timestamps.push((offset, s));
+            }
+        }
+        report
+    }
+
+    /// Check if no source of non-determinism was found.
+    ///
+    /// A missing build ID is not a source of non-determinism, but makes it
+    /// harder to compare builds.
+    pub fn is_reproducible(&self) -> bool {
+        self.build_paths.is_empty() && self.timestamps.is_empty()
+    }
+}
+
+/// Check if the string contains a date like `Oct 16 2026` or a time like
+/// `12:34:56`.
+fn has_timestamp(s: &str) -> bool {
+    let b = s.as_bytes();
+    let digits = |w: &[u8]| w.iter().all(u8::is_ascii_digit);
+
+    let date = b.windows(11).any(|w| {
+        MONTHS.iter().any(|m| w[0..3] == *m.as_bytes())
+            && w[3] == b' '
+            && (w[4] == b' ' || w[4].is_ascii_digit())
+            && w[5].is_ascii_digit()
+            && w[6] == b' '
+            && digits(&w[7..11])
+    });
+    let time = b.windows(8).any(|w| {
+        digits(&w[0..2]) && w[2] == b':' && digits(&w[3..5]) && w[5] == b':' && digits(&w[6..8])
+    });
+    date || time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::has_timestamp;
+
+    #[test]
+    fn timestamp() {
+        assert!(has_timestamp("Oct 16 2026"));
+        assert!(has_timestamp("Jan  1 1970 00:00:00"));
+        assert!(has_timestamp("built at 12:34:56"));
+        assert!(!has_timestamp("Oct 2026"));
+        assert!(!has_timestamp("12:34"));
+        assert!(!has_timestamp("version 1.2.3"));
+    }
+}
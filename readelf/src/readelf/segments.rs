@@ -0,0 +1,73 @@
+use crate::{ProgramHeader, ProgramHeaders, ReadElf};
+
+/// The program header table of an ELF file.
+///
+/// Unlike [ProgramHeaders], which is a single pass iterator, this is a handle
+/// that can be iterated over multiple times.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use readelf::ReadElf;
+///
+/// let elf = ReadElf::open("/bin/sh").unwrap();
+/// let segments = elf.segments();
+/// for ph in &segments {
+///     println!("{}", ph.segment_type);
+/// }
+/// println!("Total: {}", segments.iter().count());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Segments<'elf> {
+    elf: &'elf ReadElf<'elf>,
+}
+
+impl<'elf> Segments<'elf> {
+    pub(super) fn new(elf: &'elf ReadElf<'elf>) -> Segments<'elf> {
+        Segments { elf }
+    }
+
+    /// Get a new iterator over all the program headers.
+    pub fn iter(&self) -> ProgramHeaders<'elf> {
+        ProgramHeaders::new(self.elf)
+    }
+
+    /// Get the expected number of program header segments.
+    ///
+    /// See [ProgramHeaders::len()].
+    pub fn len(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Check if there are no segments in the ELF file.
+    ///
+    /// See [ProgramHeaders::is_empty()].
+    pub fn is_empty(&self) -> bool {
+        self.iter().is_empty()
+    }
+
+    /// Get the [ProgramHeader] at the specified index.
+    ///
+    /// See [ProgramHeaders::index()].
+    pub fn get(&self, index: usize) -> Option<ProgramHeader> {
+        self.iter().index(index)
+    }
+}
+
+impl<'elf> IntoIterator for Segments<'elf> {
+    type Item = ProgramHeader;
+    type IntoIter = ProgramHeaders<'elf>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'elf> IntoIterator for &Segments<'elf> {
+    type Item = ProgramHeader;
+    type IntoIter = ProgramHeaders<'elf>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{ReadElf, SegmentFlags};
+
+/// The size of a single loadable segment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SegmentSize {
+    /// Index of the segment in the program header table.
+    pub index: usize,
+
+    /// The flags of the segment.
+    pub flags: SegmentFlags,
+
+    /// Size in bytes of the segment in the file image.
+    pub file_size: u64,
+
+    /// Size in bytes of the segment in memory.
+    pub memory_size: u64,
+
+    /// Virtual address of the segment in memory.
+    pub virtual_address: u64,
+
+    /// Alignment of the segment in memory.
+    pub alignment: u64,
+}
+
+impl SegmentSize {
+    /// The memory needed for the segment at runtime.
+    ///
+    /// This is the memory size, including the bss, with the start rounded down
+    /// and the end rounded up to the alignment of the segment. An alignment
+    /// that is not a power of two is ignored.
+    pub fn memory_footprint(&self) -> u64 {
+        let align = match self.alignment.is_power_of_two() {
+            true => self.alignment,
+            false => 1,
+        };
+        let start = self.virtual_address & !(align - 1);
+        let end = self
+            .virtual_address
+            .saturating_add(self.memory_size)
+            .saturating_add(align - 1)
+            & !(align - 1);
+        end.saturating_sub(start)
+    }
+}
+
+/// The sizes of the loadable contents of an ELF file, similar to `size(1)`.
+///
+/// The values are calculated from the loadable segments
+/// ([crate::SegmentType::Load]):
+///
+/// - `text` is the file size of all segments that are not writable (code and
+///   read-only data);
+/// - `data` is the file size of all writable segments;
+/// - `bss` is the memory size exceeding the file size of all writable segments.
+///
+/// Get an instance with [ReadElf::size_report()].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SizeReport {
+    /// Total size of the read-only and executable contents.
+    pub text: u64,
+
+    /// Total size of the initialised writable contents.
+    pub data: u64,
+
+    /// Total size of the zero initialised writable contents.
+    pub bss: u64,
+
+    /// The loadable segments, in the order of the program header table.
+    pub segments: Vec<SegmentSize>,
+}
+
+impl SizeReport {
+    pub(super) fn new(elf: &ReadElf<'_>) -> SizeReport {
+        let mut report = SizeReport::default();
+
+        for (index, segment) in elf.program_headers().enumerate() {
+            if !segment.is_loadable() {
+                continue;
+            }
+
+            if segment.flags.is_writable() {
+                report.data = report.data.saturating_add(segment.file_size);
+                report.bss = report
+                    .bss
+                    .saturating_add(segment.memory_size.saturating_sub(segment.file_size));
+            } else {
+                report.text = report.text.saturating_add(segment.file_size);
+            }
+
+            report.segments.push(SegmentSize {
+                index,
+                flags: segment.flags,
+                file_size: segment.file_size,
+                memory_size: segment.memory_size,
+                virtual_address: segment.virtual_address,
+                alignment: segment.alignment,
+            });
+        }
+        report
+    }
+
+    /// The sum of `text`, `data` and `bss`.
+    ///
+    /// This is the `dec` (and `hex`) column of `size(1)`.
+    pub fn total(&self) -> u64 {
+        self.text.saturating_add(self.data).saturating_add(self.bss)
+    }
+
+    /// The memory needed for all loadable segments at runtime.
+    ///
+    /// This is the sum of [SegmentSize::memory_footprint()] of the segments.
+    /// Segments that share a page count it each, so this is an upper bound.
+    pub fn memory_footprint(&self) -> u64 {
+        self.segments
+            .iter()
+            .fold(0, |total, s| total.saturating_add(s.memory_footprint()))
+    }
+
+    /// Get the file size and memory size of the loadable segments, grouped by
+    /// their flags.
+    ///
+    /// The groups are in the order that the flags first occur in the program
+    /// header table.
+    pub fn by_flags(&self) -> Vec<(SegmentFlags, u64, u64)> {
+        let mut groups: Vec<(SegmentFlags, u64, u64)> = Vec::new();
+        for segment in &self.segments {
+            match groups.iter_mut().find(|g| g.0 == segment.flags) {
+                Some(g) => {
+                    g.1 = g.1.saturating_add(segment.file_size);
+                    g.2 = g.2.saturating_add(segment.memory_size);
+                }
+                None => groups.push((segment.flags, segment.file_size, segment.memory_size)),
+            }
+        }
+        groups
+    }
+}
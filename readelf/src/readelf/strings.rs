@@ -0,0 +1,117 @@
+use crate::ReadElf;
+
+/// The length of the shortest absolute path with two components, `/a/b`.
+pub(super) const MIN_PATH_LEN: usize = 4;
+
+/// Get the strings of at least `min_len` bytes in the loadable segments that
+/// are not writable, with their offset in the file.
+///
+/// These segments contain the read-only data (`.rodata`) and the dynamic
+/// string table. Segments larger than [crate::ReadOptions::max_allocation],
+/// that end after the end of the file, or that can't be read, are skipped.
+pub(super) fn read_only_strings(elf: &ReadElf<'_>, min_len: usize) -> Vec<(u64, String)> {
+    let mut found = Vec::new();
+    for ph in elf.program_headers() {
+        if !ph.is_loadable() || ph.flags.is_writable() {
+            continue;
+        }
+        let Some(range) = ph.file_range() else {
+            continue;
+        };
+        let Some(bytes) = elf.read_bytes(range.clone()) else {
+            continue;
+        };
+
+        for (offset, s) in strings(&bytes, min_len) {
+            found.push((range.start + offset as u64, s.to_string()));
+        }
+    }
+    found
+}
+
+/// Get the NUL terminated strings of printable ASCII characters of at least
+/// `min_len` bytes, with their offset.
+fn strings(bytes: &[u8], min_len: usize) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+    bytes.split(|b| *b == 0).filter_map(move |s| {
+        let start = offset;
+        offset += s.len() + 1;
+
+        // Strings are often merged, so that a string may start after
+        // non-printable bytes.
+        let printable = s
+            .iter()
+            .rposition(|b| !(b.is_ascii_graphic() || *b == b' '))
+            .map_or(0, |p| p + 1);
+        let s = &s[printable..];
+        if s.len() < min_len {
+            return None;
+        }
+        Some((start + printable, std::str::from_utf8(s).ok()?))
+    })
+}
+
+/// Get the absolute paths in a string, with their offset in the string.
+///
+/// A path starts with a `/` at the start of the string or after a space,
+/// quote, `=`, `:` or `(`, and has at least two components. It ends at white
+/// space, a quote, `:` or `)`. URLs like `https://host/path` are not paths.
+pub(super) fn absolute_paths(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let is_end = |c: char| c.is_ascii_whitespace() || matches!(c, '"' | '\'' | ':' | ')');
+    s.match_indices('/').filter_map(move |(start, _)| {
+        let before = s[..start].chars().next_back();
+        if before.is_some_and(|c| !(is_end(c) || matches!(c, '=' | '('))) {
+            return None;
+        }
+        let path = &s[start..];
+        let path = &path[..path.find(is_end).unwrap_or(path.len())];
+
+        // At least two components, e.g. `/bin/sh`.
+        let components = path.split('/').filter(|c| !c.is_empty()).count();
+        (components >= 2 && !path.starts_with("//")).then_some((start, path))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{absolute_paths, strings};
+
+    #[test]
+    fn find_strings() {
+        let bytes = b"\x01\x02/home/user/src\0short\0\0\xffBuilt on Oct 16 2026";
+        let found: Vec<(usize, &str)> = strings(bytes, 8).collect();
+        assert_eq!(
+            found,
+            vec![(2, "/home/user/src"), (25, "Built on Oct 16 2026")]
+        );
+
+        let found: Vec<(usize, &str)> = strings(bytes, 4).collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, "/home/user/src"),
+                (17, "short"),
+                (25, "Built on Oct 16 2026")
+            ]
+        );
+    }
+
+    #[test]
+    fn paths() {
+        let found: Vec<(usize, &str)> =
+            absolute_paths("/bin/sh -c 'ls /tmp/x' PATH=/usr/bin:/bin/x (/a/b) a/b/c").collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, "/bin/sh"),
+                (15, "/tmp/x"),
+                (28, "/usr/bin"),
+                (37, "/bin/x"),
+                (45, "/a/b")
+            ]
+        );
+
+        assert_eq!(absolute_paths("https://example.com/a/b").count(), 0);
+        assert_eq!(absolute_paths("/ and //x/y and /bin").count(), 0);
+    }
+}
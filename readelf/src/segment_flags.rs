@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::{BitAnd, BitOr, Not};
 
 /// The flags associated with a segment in the program header.
 ///
@@ -12,7 +13,19 @@ use std::fmt;
 /// let f = SegmentFlags::from(SegmentFlags::R + SegmentFlags::X);
 /// println!("{:?}", f.to_string());
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// The flags can be combined with the bit operators, with either another
+/// [SegmentFlags] or one of the constants.
+///
+/// ```rust
+/// use readelf::SegmentFlags;
+///
+/// let f = SegmentFlags::from(SegmentFlags::R) | SegmentFlags::X;
+/// assert!(f.contains(SegmentFlags::X));
+/// assert!(!f.contains(SegmentFlags::W));
+/// assert_eq!(f & !SegmentFlags::from(SegmentFlags::X), SegmentFlags::from(SegmentFlags::R));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct SegmentFlags {
     flags: u32,
 }
@@ -35,17 +48,67 @@ impl SegmentFlags {
     pub fn flags(&self) -> u32 {
         self.flags
     }
+
+    /// Check if the segment is readable, [SegmentFlags::R].
+    #[must_use]
+    pub fn is_readable(&self) -> bool {
+        self.contains(SegmentFlags::R)
+    }
+
+    /// Check if the segment is writable, [SegmentFlags::W].
+    #[must_use]
+    pub fn is_writable(&self) -> bool {
+        self.contains(SegmentFlags::W)
+    }
+
+    /// Check if the segment is executable, [SegmentFlags::X].
+    #[must_use]
+    pub fn is_executable(&self) -> bool {
+        self.contains(SegmentFlags::X)
+    }
+
+    /// Check if all the bits of `flags` are set.
+    ///
+    /// The `flags` may be one of the constants, or another [SegmentFlags].
+    /// Checking for [SegmentFlags::NONE] is always `true`.
+    #[must_use]
+    pub fn contains<T: Into<u32>>(&self, flags: T) -> bool {
+        let flags = flags.into();
+        self.flags & flags == flags
+    }
+}
+
+impl<T: Into<u32>> BitOr<T> for SegmentFlags {
+    type Output = SegmentFlags;
+
+    fn bitor(self, rhs: T) -> SegmentFlags {
+        SegmentFlags::from(self.flags | rhs.into())
+    }
+}
+
+impl<T: Into<u32>> BitAnd<T> for SegmentFlags {
+    type Output = SegmentFlags;
+
+    fn bitand(self, rhs: T) -> SegmentFlags {
+        SegmentFlags::from(self.flags & rhs.into())
+    }
+}
+
+impl Not for SegmentFlags {
+    type Output = SegmentFlags;
+
+    fn not(self) -> SegmentFlags {
+        SegmentFlags::from(!self.flags)
+    }
 }
 
 impl From<u32> for SegmentFlags {
-    #[must_use]
     fn from(v: u32) -> Self {
         SegmentFlags { flags: v }
     }
 }
 
 impl From<SegmentFlags> for u32 {
-    #[must_use]
     fn from(v: SegmentFlags) -> Self {
         v.flags
     }
@@ -122,4 +185,42 @@ mod tests {
 
         assert_eq!(flags.flags(), SegmentFlags::X);
     }
+
+    #[test]
+    fn bit_operators() {
+        let r = SegmentFlags::from(SegmentFlags::R);
+        let x = SegmentFlags::from(SegmentFlags::X);
+
+        assert_eq!(r | x, SegmentFlags::from(5));
+        assert_eq!(r | SegmentFlags::W, SegmentFlags::from(6));
+        assert_eq!((r | x) & x, x);
+        assert_eq!((r | x) & SegmentFlags::W, SegmentFlags::from(0));
+        assert_eq!(!x, SegmentFlags::from(0xFFFF_FFFE));
+        assert_eq!(SegmentFlags::from(7) & !x, SegmentFlags::from(6));
+    }
+
+    #[test]
+    fn contains() {
+        let f = SegmentFlags::from(SegmentFlags::R | SegmentFlags::X);
+        assert!(f.contains(SegmentFlags::R));
+        assert!(f.contains(SegmentFlags::X));
+        assert!(!f.contains(SegmentFlags::W));
+        assert!(f.contains(SegmentFlags::R | SegmentFlags::X));
+        assert!(!f.contains(SegmentFlags::R | SegmentFlags::W));
+        assert!(f.contains(SegmentFlags::from(SegmentFlags::X)));
+        assert!(f.contains(SegmentFlags::NONE));
+    }
+
+    #[test]
+    fn predicates() {
+        let f = SegmentFlags::from(SegmentFlags::R | SegmentFlags::X);
+        assert!(f.is_readable());
+        assert!(!f.is_writable());
+        assert!(f.is_executable());
+
+        let f = SegmentFlags::from(SegmentFlags::W);
+        assert!(!f.is_readable());
+        assert!(f.is_writable());
+        assert!(!f.is_executable());
+    }
 }
@@ -1,4 +1,19 @@
+use crate::Machine;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+use std::sync::{PoisonError, RwLock};
+
+/// The segment types reserved for OS and processor specific semantics, from
+/// `PT_LOOS` to `PT_HIPROC`.
+const PT_LOOS_HIPROC: RangeInclusive<u32> = 0x6000_0000..=0x7FFF_FFFF;
+
+/// The names of segment types registered with [SegmentType::register_name()],
+/// by the value and the machine.
+static REGISTERED_NAMES: RwLock<BTreeMap<(u32, Option<u16>), String>> =
+    RwLock::new(BTreeMap::new());
 
 /// The segment type of a program header in the ELF file.
 ///
@@ -51,7 +66,10 @@ use std::fmt;
 ///   }
 /// }
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// Two values are compared, hashed and ordered by their value, so that
+/// `SegmentType::Unknown(1)` equals `SegmentType::Load`.
+#[derive(Clone, Copy, Debug)]
 #[repr(u32)]
 pub enum SegmentType {
     /// Program header table entry is unused.
@@ -100,6 +118,75 @@ pub enum SegmentType {
     Unknown(u32),
 }
 
+impl SegmentType {
+    /// Register the name of an OS or processor specific segment type, such
+    /// as one of a proprietary toolchain.
+    ///
+    /// The name is for all files, or only for files of the `machine` given,
+    /// which is useful for the processor specific range. A name registered
+    /// for all files is used by [fmt::Display] instead of `Segment 0xNNNNNNNN`
+    /// for all [SegmentType] instances of the process. Registering a name
+    /// again for the same value and machine replaces it.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the value is not in the range `PT_LOOS`
+    /// (`0x60000000`) to `PT_HIPROC` (`0x7FFFFFFF`).
+    ///
+    /// # Example
+    /// ```rust
+    /// use readelf::{Machine, SegmentType};
+    ///
+    /// let arm = Machine::from(Machine::ARM);
+    /// assert!(SegmentType::register_name(0x7000_0010, Some(arm), "VENDOR_DATA"));
+    /// let t = SegmentType::from(0x7000_0010);
+    /// assert_eq!(t.registered_name(Some(arm)).as_deref(), Some("VENDOR_DATA"));
+    /// assert_eq!(t.registered_name(None), None);
+    /// ```
+    pub fn register_name(value: u32, machine: Option<Machine>, name: &str) -> bool {
+        if !PT_LOOS_HIPROC.contains(&value) {
+            return false;
+        }
+        REGISTERED_NAMES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((value, machine.map(u16::from)), name.to_string());
+        true
+    }
+
+    /// Remove the name of a segment type registered with
+    /// [SegmentType::register_name()] for the same machine.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if a name was registered for the value and machine.
+    pub fn unregister_name(value: u32, machine: Option<Machine>) -> bool {
+        REGISTERED_NAMES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&(value, machine.map(u16::from)))
+            .is_some()
+    }
+
+    /// Get the name registered with [SegmentType::register_name()].
+    ///
+    /// # Returns
+    ///
+    /// Returns the name registered for the `machine`, else the name
+    /// registered for all files. Returns [Option::None] if no name was
+    /// registered.
+    pub fn registered_name(&self, machine: Option<Machine>) -> Option<String> {
+        let value = u32::from(*self);
+        let registered = REGISTERED_NAMES
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        machine
+            .and_then(|m| registered.get(&(value, Some(u16::from(m)))))
+            .or_else(|| registered.get(&(value, None)))
+            .cloned()
+    }
+}
+
 impl From<u32> for SegmentType {
     fn from(v: u32) -> SegmentType {
         match v {
@@ -132,6 +219,34 @@ impl From<SegmentType> for u32 {
     }
 }
 
+// The traits use the value, so that an unknown variant with the value of a
+// known variant is the same as the known variant.
+impl PartialEq for SegmentType {
+    fn eq(&self, other: &Self) -> bool {
+        u32::from(*self) == u32::from(*other)
+    }
+}
+
+impl Eq for SegmentType {}
+
+impl Hash for SegmentType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u32::from(*self).hash(state);
+    }
+}
+
+impl PartialOrd for SegmentType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SegmentType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u32::from(*self).cmp(&u32::from(*other))
+    }
+}
+
 impl fmt::Display for SegmentType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Don't explicitly use the variant, so Unknown(x) will map to the
@@ -145,7 +260,10 @@ impl fmt::Display for SegmentType {
             4 => write!(f, "Note"),
             6 => write!(f, "Program Header"),
             7 => write!(f, "Thread Local Storage"),
-            _ => write!(f, "Segment 0x{:0>8X}", v),
+            _ => match self.registered_name(None) {
+                Some(name) => write!(f, "{}", name),
+                None => write!(f, "Segment 0x{:0>8X}", v),
+            },
         }
     }
 }
@@ -153,6 +271,8 @@ impl fmt::Display for SegmentType {
 #[cfg(test)]
 mod tests {
     use super::SegmentType;
+    use crate::Machine;
+    use std::collections::HashSet;
 
     #[test]
     fn from_value() {
@@ -188,6 +308,36 @@ mod tests {
         assert_eq!(u32::from(SegmentType::Unknown(0xFFFFFFFF)), 0xFFFFFFFF);
     }
 
+    #[test]
+    fn register_name() {
+        // Use values that no other test formats, as the names are shared by
+        // the process.
+        let arm = Machine::from(Machine::ARM);
+        assert!(SegmentType::register_name(0x6FFF_0001, None, "OS_DATA"));
+        assert!(SegmentType::register_name(
+            0x7FFF_0001,
+            Some(arm),
+            "ARM_DATA"
+        ));
+        assert!(!SegmentType::register_name(0x8000_0000, None, "INVALID"));
+        assert!(!SegmentType::register_name(8, None, "INVALID"));
+
+        let os = SegmentType::from(0x6FFF_0001);
+        assert_eq!(os.to_string(), "OS_DATA");
+        assert_eq!(os.registered_name(Some(arm)).as_deref(), Some("OS_DATA"));
+
+        let proc = SegmentType::from(0x7FFF_0001);
+        assert_eq!(proc.to_string(), "Segment 0x7FFF0001");
+        assert_eq!(proc.registered_name(Some(arm)).as_deref(), Some("ARM_DATA"));
+        let x86 = Machine::from(Machine::X86_64);
+        assert_eq!(proc.registered_name(Some(x86)), None);
+
+        assert!(SegmentType::unregister_name(0x6FFF_0001, None));
+        assert!(!SegmentType::unregister_name(0x7FFF_0001, None));
+        assert!(SegmentType::unregister_name(0x7FFF_0001, Some(arm)));
+        assert_eq!(os.to_string(), "Segment 0x6FFF0001");
+    }
+
     #[test]
     fn segment_type_to_string() {
         assert_eq!(SegmentType::Null.to_string(), "Null");
@@ -212,4 +362,30 @@ mod tests {
             "Segment 0x8000FFFF"
         );
     }
+
+    #[test]
+    fn ordering() {
+        let mut types = vec![
+            SegmentType::from(0x6474E550),
+            SegmentType::from(8),
+            SegmentType::Load,
+            SegmentType::ThreadLocalStorage,
+            SegmentType::Unknown(2),
+            SegmentType::Null,
+        ];
+        types.sort();
+        let values: Vec<u32> = types.into_iter().map(u32::from).collect();
+        assert_eq!(values, vec![0, 1, 2, 7, 8, 0x6474E550]);
+    }
+
+    #[test]
+    fn compare_by_value() {
+        assert_eq!(SegmentType::Unknown(1), SegmentType::Load);
+        assert_ne!(SegmentType::Unknown(2), SegmentType::Load);
+
+        let types: HashSet<SegmentType> = [SegmentType::Unknown(1), SegmentType::Load]
+            .into_iter()
+            .collect();
+        assert_eq!(types.len(), 1);
+    }
 }
@@ -0,0 +1,201 @@
+//! Test suite creating flat memory images of the loadable segments.
+
+use readelf::*;
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder32};
+use common::segment::segment;
+
+/// A firmware image with code at 0x08000000, initialised data stored after
+/// it in flash, and a bss only in RAM.
+fn firmware() -> ElfBuilder32 {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.set_machine(Machine::from(Machine::ARM));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x0800_0000,
+        4,
+        4,
+    ));
+    elf_builder.add_segment(&ProgramHeader {
+        virtual_address: 0x2000_0000,
+        ..segment(
+            SegmentType::Load,
+            SegmentFlags::R,
+            0x1004,
+            0x0800_0008,
+            2,
+            0x100,
+        )
+    });
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1006,
+        0x2000_0100,
+        0,
+        0x400,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x0,
+        0x0,
+        0x10,
+        0x10,
+    ));
+    for (i, b) in [0x11, 0x22, 0x33, 0x44, 0x55, 0x66].iter().enumerate() {
+        elf_builder.write_u8(0x1000 + i, *b);
+    }
+    elf_builder
+}
+
+#[test]
+fn binary_image() {
+    let elf_builder = firmware();
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let image = elf.binary_image(&BinaryImageOptions::default()).unwrap();
+    assert_eq!(image.base, 0x0800_0000);
+    assert_eq!(
+        image.data,
+        vec![0x11, 0x22, 0x33, 0x44, 0x00, 0x00, 0x00, 0x00, 0x55, 0x66]
+    );
+}
+
+#[test]
+fn binary_image_options() {
+    let elf_builder = firmware();
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let options = BinaryImageOptions {
+        base: Some(0x07FF_FFFE),
+        fill: 0xFF,
+        ..Default::default()
+    };
+    let image = elf.binary_image(&options).unwrap();
+    assert_eq!(image.base, 0x07FF_FFFE);
+    assert_eq!(
+        image.data,
+        vec![0xFF, 0xFF, 0x11, 0x22, 0x33, 0x44, 0xFF, 0xFF, 0xFF, 0xFF, 0x55, 0x66]
+    );
+
+    let options = BinaryImageOptions {
+        base: Some(0x0800_0001),
+        ..Default::default()
+    };
+    assert_eq!(elf.binary_image(&options), None);
+
+    let options = BinaryImageOptions {
+        max_size: 9,
+        ..Default::default()
+    };
+    assert_eq!(elf.binary_image(&options), None);
+}
+
+#[test]
+fn binary_image_no_segments() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.binary_image(&BinaryImageOptions::default()), None);
+}
+
+#[test]
+fn binary_image_truncated() {
+    // The segment ends far after the end of the file.
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x0800_0000,
+        0x1000_0000,
+        0x1000_0000,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.binary_image(&BinaryImageOptions::default()), None);
+}
+
+#[test]
+fn intel_hex() {
+    let mut elf_builder = firmware();
+    elf_builder.set_entry(0x0800_0001);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let image = elf.binary_image(&BinaryImageOptions::default()).unwrap();
+
+    assert_eq!(
+        image.to_ihex(8).unwrap(),
+        ":020000040800F2\r\n\
+         :0800000011223344000000004E\r\n\
+         :0200080055663B\r\n\
+         :0400000508000001EE\r\n\
+         :00000001FF\r\n"
+    );
+    assert_eq!(image.to_ihex(0), None);
+}
+
+#[test]
+fn intel_hex_64k_boundary() {
+    let image = BinaryImage {
+        base: 0xFFFE,
+        data: vec![1, 2, 3],
+        entry: 0,
+    };
+    assert_eq!(
+        image.to_ihex(16).unwrap(),
+        ":02FFFE000102FE\r\n\
+         :020000040001F9\r\n\
+         :0100000003FC\r\n\
+         :00000001FF\r\n"
+    );
+
+    let image = BinaryImage {
+        base: 0xFFFF_FFFF,
+        data: vec![1, 2],
+        entry: 0,
+    };
+    assert_eq!(image.to_ihex(16), None);
+}
+
+#[test]
+fn motorola_srec() {
+    let mut elf_builder = firmware();
+    elf_builder.set_entry(0x0800_0001);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let image = elf.binary_image(&BinaryImageOptions::default()).unwrap();
+
+    assert_eq!(
+        image.to_srec(8).unwrap(),
+        "S0030000FC\r\n\
+         S30D08000000112233440000000040\r\n\
+         S3070800000855662D\r\n\
+         S5030002FA\r\n\
+         S70508000001F1\r\n"
+    );
+    assert_eq!(image.to_srec(0), None);
+    assert_eq!(image.to_srec(251), None);
+}
+
+#[test]
+fn motorola_srec_address_size() {
+    let image = BinaryImage {
+        base: 0x100,
+        data: vec![1, 2],
+        entry: 0,
+    };
+    assert_eq!(
+        image.to_srec(16).unwrap(),
+        "S0030000FC\r\nS10501000102F6\r\nS5030001FB\r\nS9030000FC\r\n"
+    );
+
+    let image = BinaryImage {
+        base: 0xFFFF,
+        ..image
+    };
+    assert_eq!(
+        image.to_srec(16).unwrap(),
+        "S0030000FC\r\nS20600FFFF0102F8\r\nS5030001FB\r\nS804000000FB\r\n"
+    );
+}
@@ -1,2 +1,7 @@
+// Each integration test is its own crate, and only uses a subset of the common
+// modules.
+#![allow(dead_code)]
+
 pub(crate) mod builder;
 pub(crate) mod config;
+pub(crate) mod segment;
@@ -0,0 +1,23 @@
+use readelf::{ProgramHeader, SegmentFlags, SegmentType};
+
+/// A segment with the physical address the same as the virtual address, and
+/// aligned to 4KiB.
+pub fn segment(
+    segment_type: SegmentType,
+    flags: u32,
+    file_offset: u64,
+    virtual_address: u64,
+    file_size: u64,
+    memory_size: u64,
+) -> ProgramHeader {
+    ProgramHeader {
+        segment_type,
+        flags: SegmentFlags::from(flags),
+        file_offset,
+        virtual_address,
+        physical_address: virtual_address,
+        file_size,
+        memory_size,
+        alignment: 0x1000,
+    }
+}
@@ -0,0 +1,334 @@
+//! Test suite resolving the shared objects that an ELF file depends on.
+
+use readelf::*;
+use std::path::{Path, PathBuf};
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder64};
+
+const DT_NEEDED: u64 = 1;
+const DT_STRTAB: u64 = 5;
+const DT_STRSZ: u64 = 10;
+const DT_SONAME: u64 = 14;
+const DT_RPATH: u64 = 15;
+const DT_RUNPATH: u64 = 29;
+
+/// Build a shared object with the dynamic entries, where the strings are
+/// written to the string table.
+fn shared_object(machine: u16, strings: &[(u64, &str)]) -> Vec<u8> {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Dynamic)
+        .set_machine(Machine::from(machine));
+
+    let mut strtab = 1;
+    let mut entries = Vec::new();
+    for (tag, s) in strings {
+        for (i, b) in s.bytes().enumerate() {
+            elf_builder.write_u8(0x1000 + strtab + i, b);
+        }
+        entries.push((*tag, strtab as u64));
+        strtab += s.len() + 1;
+    }
+    entries.push((DT_STRTAB, 0x11000));
+    entries.push((DT_STRSZ, strtab as u64));
+    entries.push((0, 0));
+    for (i, (tag, value)) in entries.iter().enumerate() {
+        elf_builder.write_u64(0x800 + i * 16, *tag);
+        elf_builder.write_u64(0x800 + i * 16 + 8, *value);
+    }
+
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0,
+        virtual_address: 0x10000,
+        physical_address: 0x10000,
+        file_size: 0x2000,
+        memory_size: 0x2000,
+        alignment: 0x1000,
+    });
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Dynamic,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x800,
+        virtual_address: 0x10800,
+        physical_address: 0x10800,
+        file_size: (entries.len() * 16) as u64,
+        memory_size: (entries.len() * 16) as u64,
+        alignment: 8,
+    });
+    elf_builder.buffer().to_vec()
+}
+
+/// A directory with files for a test, removed when dropped.
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new(name: &str) -> TestDir {
+        let dir = std::env::temp_dir().join(format!("readelf-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        TestDir(dir)
+    }
+
+    fn write(&self, path: &str, contents: &[u8]) -> PathBuf {
+        let path = self.0.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn paths(deps: &[Dependency]) -> Vec<(&str, Option<&Path>)> {
+    deps.iter()
+        .map(|d| (d.name.as_str(), d.path.as_deref()))
+        .collect()
+}
+
+#[test]
+fn dynamic_strings() {
+    let elf = shared_object(
+        Machine::X86_64,
+        &[
+            (DT_SONAME, "libfoo.so.1"),
+            (DT_NEEDED, "libbar.so.2"),
+            (DT_NEEDED, "libc.so.6"),
+            (DT_RUNPATH, "$ORIGIN/../lib"),
+        ],
+    );
+    let elf = ReadElf::from_vec(elf).unwrap();
+    assert_eq!(elf.needed_libraries(), vec!["libbar.so.2", "libc.so.6"]);
+    assert_eq!(elf.soname().as_deref(), Some("libfoo.so.1"));
+    assert_eq!(elf.runpath().as_deref(), Some("$ORIGIN/../lib"));
+    assert_eq!(elf.rpath(), None);
+    assert_eq!(elf.vaddr_to_offset(0x11001), Some(0x1001));
+    assert_eq!(elf.vaddr_to_offset(0x12000), None);
+}
+
+#[test]
+fn dynamic_strings_long() {
+    // The strings are read in chunks, so use a name longer than a chunk.
+    let name = format!("lib{}.so", "x".repeat(0x200));
+    let elf = shared_object(Machine::X86_64, &[(DT_NEEDED, &name)]);
+
+    let read = ReadElf::from_slice(&elf).unwrap();
+    assert_eq!(read.needed_libraries(), vec![name.clone()]);
+
+    let options = ReadOptions {
+        max_allocation: name.len() as u64,
+        ..Default::default()
+    };
+    let read = ReadElf::from_slice_with_options(&elf, options).unwrap();
+    assert_eq!(read.needed_libraries(), Vec::<String>::new());
+}
+
+#[test]
+fn resolve_tree() {
+    let dir = TestDir::new("resolve-tree");
+    let exe = dir.write(
+        "app/bin/app",
+        &shared_object(
+            Machine::X86_64,
+            &[
+                (DT_NEEDED, "liba.so"),
+                (DT_NEEDED, "libmissing.so"),
+                (DT_NEEDED, "libb.so"),
+                (DT_RUNPATH, "$ORIGIN/../lib"),
+            ],
+        ),
+    );
+    dir.write(
+        "app/lib/liba.so",
+        &shared_object(Machine::X86_64, &[(DT_NEEDED, "libb.so")]),
+    );
+    dir.write("sys/libb.so", &shared_object(Machine::X86_64, &[]));
+
+    // The object for another machine is skipped.
+    dir.write("app/lib/libb.so", &shared_object(Machine::AARCH64, &[]));
+
+    let config = SearchConfig {
+        default_dirs: vec![dir.path("sys")],
+        ..Default::default()
+    };
+    let deps = resolve_dependencies(&exe, &config).unwrap();
+    let liba = dir.path("app/bin/../lib/liba.so");
+    let libb = dir.path("sys/libb.so");
+    assert_eq!(
+        paths(&deps),
+        vec![
+            ("liba.so", Some(liba.as_path())),
+            ("libmissing.so", None),
+            ("libb.so", Some(libb.as_path())),
+        ]
+    );
+    assert_eq!(
+        paths(&deps[0].dependencies),
+        vec![("libb.so", Some(libb.as_path()))]
+    );
+    assert!(deps[0].is_resolved());
+    assert!(!deps[1].is_resolved());
+}
+
+#[test]
+fn resolve_rpath_inherited() {
+    let dir = TestDir::new("resolve-rpath");
+    let exe = dir.write(
+        "bin/app",
+        &shared_object(
+            Machine::X86_64,
+            &[(DT_NEEDED, "liba.so"), (DT_RPATH, "$ORIGIN/../rpath")],
+        ),
+    );
+    dir.write(
+        "rpath/liba.so",
+        &shared_object(Machine::X86_64, &[(DT_NEEDED, "libb.so")]),
+    );
+    dir.write("rpath/libb.so", &shared_object(Machine::X86_64, &[]));
+    dir.write("library/libb.so", &shared_object(Machine::X86_64, &[]));
+
+    // The `DT_RPATH` of the executable is used for the dependencies of
+    // `liba.so`, before the library path.
+    let config = SearchConfig {
+        library_path: vec![dir.path("library")],
+        default_dirs: vec![],
+        ..Default::default()
+    };
+    let deps = resolve_dependencies(&exe, &config).unwrap();
+    assert_eq!(
+        deps[0].dependencies[0].path,
+        Some(dir.path("bin/../rpath/libb.so"))
+    );
+
+    // The `DT_RUNPATH` of `liba.so` disables the inherited `DT_RPATH`.
+    dir.write(
+        "rpath/liba.so",
+        &shared_object(
+            Machine::X86_64,
+            &[(DT_NEEDED, "libb.so"), (DT_RUNPATH, "/nonexistent")],
+        ),
+    );
+    let deps = resolve_dependencies(&exe, &config).unwrap();
+    assert_eq!(
+        deps[0].dependencies[0].path,
+        Some(dir.path("library/libb.so"))
+    );
+}
+
+#[test]
+fn resolve_not_elf() {
+    let dir = TestDir::new("resolve-not-elf");
+    let file = dir.write("file.txt", b"text");
+    assert_eq!(resolve_dependencies(file, &SearchConfig::default()), None);
+}
+
+#[test]
+fn resolve_cache() {
+    let dir = TestDir::new("resolve-cache");
+    let exe = dir.write(
+        "bin/app",
+        &shared_object(Machine::X86_64, &[(DT_NEEDED, "libc.so.6")]),
+    );
+    dir.write("lib32/libc.so.6", &shared_object(Machine::INTEL_386, &[]));
+    dir.write("lib64/libc.so.6", &shared_object(Machine::X86_64, &[]));
+    dir.write("lib/libc.so.6", &shared_object(Machine::X86_64, &[]));
+
+    // The entry for another machine is skipped, and the cache is searched
+    // before the default directories.
+    let entry = |path: &str| LdSoCacheEntry {
+        name: "libc.so.6".to_string(),
+        path: dir.path(path),
+        flags: 0x0303,
+    };
+    let config = SearchConfig {
+        cache: Some(LdSoCache {
+            entries: vec![entry("lib32/libc.so.6"), entry("lib64/libc.so.6")],
+        }),
+        default_dirs: vec![dir.path("lib")],
+        ..Default::default()
+    };
+    let deps = resolve_dependencies(&exe, &config).unwrap();
+    assert_eq!(deps[0].path, Some(dir.path("lib64/libc.so.6")));
+}
+
+#[test]
+fn resolve_sysroot() {
+    let dir = TestDir::new("resolve-sysroot");
+    let exe = dir.write(
+        "sysroot/usr/bin/app",
+        &shared_object(
+            Machine::AARCH64,
+            &[
+                (DT_NEEDED, "liba.so"),
+                (DT_NEEDED, "libc.so.6"),
+                (DT_NEEDED, "/opt/lib/libabs.so"),
+                (DT_RUNPATH, "/opt/lib"),
+            ],
+        ),
+    );
+    dir.write(
+        "sysroot/opt/lib/liba.so",
+        &shared_object(Machine::AARCH64, &[]),
+    );
+    dir.write(
+        "sysroot/opt/lib/libabs.so",
+        &shared_object(Machine::AARCH64, &[]),
+    );
+    dir.write(
+        "sysroot/lib/aarch64-linux-gnu/libc.so.6",
+        &shared_object(Machine::AARCH64, &[]),
+    );
+
+    // The directories of the target are searched inside the sysroot, not on
+    // the host.
+    let elf = ReadElf::open(&exe).unwrap();
+    let config = SearchConfig {
+        sysroot: Some(dir.path("sysroot")),
+        ..SearchConfig::for_target(&elf)
+    };
+    let deps = resolve_dependencies(&exe, &config).unwrap();
+    let liba = dir.path("sysroot/opt/lib/liba.so");
+    let libc = dir.path("sysroot/lib/aarch64-linux-gnu/libc.so.6");
+    let libabs = dir.path("sysroot/opt/lib/libabs.so");
+    assert_eq!(
+        paths(&deps),
+        vec![
+            ("liba.so", Some(liba.as_path())),
+            ("libc.so.6", Some(libc.as_path())),
+            ("/opt/lib/libabs.so", Some(libabs.as_path())),
+        ]
+    );
+}
+
+#[test]
+fn search_config_for_target() {
+    let elf = ReadElf::from_vec(shared_object(Machine::AARCH64, &[])).unwrap();
+    let config = SearchConfig::for_target(&elf);
+    assert_eq!(
+        config.default_dirs,
+        vec![
+            PathBuf::from("/lib/aarch64-linux-gnu"),
+            PathBuf::from("/usr/lib/aarch64-linux-gnu"),
+            PathBuf::from("/lib64"),
+            PathBuf::from("/usr/lib64"),
+            PathBuf::from("/lib"),
+            PathBuf::from("/usr/lib"),
+        ]
+    );
+    assert_eq!(config.sysroot, None);
+
+    // There are no multiarch directories for an unknown machine.
+    let elf = ReadElf::from_vec(shared_object(Machine::NONE, &[])).unwrap();
+    let config = SearchConfig::for_target(&elf);
+    assert_eq!(config.default_dirs[0], PathBuf::from("/lib64"));
+}
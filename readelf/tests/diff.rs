@@ -0,0 +1,299 @@
+//! Test suite comparing the structure of two ELF files.
+
+use readelf::*;
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder64};
+use common::segment::segment;
+
+#[test]
+fn diff_identical() {
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    ));
+    let elf_a = ReadElf::from_slice(a.buffer()).unwrap();
+    let elf_b = ReadElf::from_slice(a.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert!(d.is_empty());
+}
+
+#[test]
+fn diff_header_fields() {
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.set_entry(0x1000);
+    let mut b = ElfBuilder64::new(Endian::Little);
+    b.set_entry(0x2000).set_flags(0x5);
+
+    let elf_a = ReadElf::from_slice(a.buffer()).unwrap();
+    let elf_b = ReadElf::from_slice(b.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert_eq!(
+        d.header,
+        vec![
+            HeaderChange::Entry(0x1000, 0x2000),
+            HeaderChange::Flags(0x0, 0x5)
+        ]
+    );
+    assert!(d.segments.is_empty());
+}
+
+#[test]
+fn diff_segment_added_removed() {
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    ));
+    a.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x1100,
+        0x11100,
+        0x20,
+        0x20,
+    ));
+    let mut b = ElfBuilder64::new(Endian::Little);
+    b.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    ));
+    b.add_segment(&segment(
+        SegmentType::Dynamic,
+        SegmentFlags::R,
+        0x1100,
+        0x11100,
+        0x20,
+        0x20,
+    ));
+
+    let elf_a = ReadElf::from_slice(a.buffer()).unwrap();
+    let elf_b = ReadElf::from_slice(b.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert!(d.header.is_empty());
+    assert_eq!(
+        d.segments,
+        vec![
+            SegmentChange::Removed {
+                index: 1,
+                segment: segment(
+                    SegmentType::Note,
+                    SegmentFlags::R,
+                    0x1100,
+                    0x11100,
+                    0x20,
+                    0x20
+                )
+            },
+            SegmentChange::Added {
+                index: 1,
+                segment: segment(
+                    SegmentType::Dynamic,
+                    SegmentFlags::R,
+                    0x1100,
+                    0x11100,
+                    0x20,
+                    0x20
+                )
+            }
+        ]
+    );
+}
+
+#[test]
+fn diff_segment_count() {
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    ));
+    let mut b = ElfBuilder64::new(Endian::Little);
+    b.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    ));
+    b.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x2000,
+        0x12000,
+        0x100,
+        0x100,
+    ));
+
+    let elf_a = ReadElf::from_slice(a.buffer()).unwrap();
+    let elf_b = ReadElf::from_slice(b.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert_eq!(d.header, vec![HeaderChange::ProgramHeaderCount(1, 2)]);
+    assert_eq!(
+        d.segments,
+        vec![SegmentChange::Added {
+            index: 1,
+            segment: segment(
+                SegmentType::Load,
+                SegmentFlags::R,
+                0x2000,
+                0x12000,
+                0x100,
+                0x100
+            )
+        }]
+    );
+}
+
+#[test]
+fn diff_segment_resized_moved_flags() {
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    ));
+    let mut b = ElfBuilder64::new(Endian::Little);
+    b.add_segment(&ProgramHeader {
+        physical_address: 0x11000,
+        ..segment(
+            SegmentType::Load,
+            SegmentFlags::R + SegmentFlags::X,
+            0x2000,
+            0x12000,
+            0x200,
+            0x200,
+        )
+    });
+
+    let elf_a = ReadElf::from_slice(a.buffer()).unwrap();
+    let elf_b = ReadElf::from_slice(b.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert!(d.header.is_empty());
+    assert_eq!(
+        d.segments,
+        vec![
+            SegmentChange::Resized {
+                old_index: 0,
+                new_index: 0,
+                segment_type: SegmentType::Load,
+                old_size: (0x100, 0x100),
+                new_size: (0x200, 0x200),
+            },
+            SegmentChange::Moved {
+                old_index: 0,
+                new_index: 0,
+                segment_type: SegmentType::Load,
+                old_location: (0x1000, 0x11000),
+                new_location: (0x2000, 0x12000),
+            },
+            SegmentChange::Flags {
+                old_index: 0,
+                new_index: 0,
+                segment_type: SegmentType::Load,
+                old_flags: SegmentFlags::from(SegmentFlags::R),
+                new_flags: SegmentFlags::from(SegmentFlags::R + SegmentFlags::X),
+            }
+        ]
+    );
+}
+
+#[test]
+fn diff_segment_physical_address_alignment() {
+    let load = segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    );
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.add_segment(&load);
+    let mut b = ElfBuilder64::new(Endian::Little);
+    b.add_segment(&ProgramHeader {
+        physical_address: 0x8001_1000,
+        alignment: 0x10000,
+        ..load
+    });
+
+    let elf_a = ReadElf::from_slice(a.buffer()).unwrap();
+    let elf_b = ReadElf::from_slice(b.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert_eq!(
+        d.segments,
+        vec![
+            SegmentChange::PhysicalAddress {
+                old_index: 0,
+                new_index: 0,
+                segment_type: SegmentType::Load,
+                old_address: 0x11000,
+                new_address: 0x8001_1000,
+            },
+            SegmentChange::Alignment {
+                old_index: 0,
+                new_index: 0,
+                segment_type: SegmentType::Load,
+                old_alignment: 0x1000,
+                new_alignment: 0x10000,
+            }
+        ]
+    );
+}
+
+#[test]
+fn diff_segment_truncated() {
+    let load = segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x11000,
+        0x100,
+        0x100,
+    );
+    let mut a = ElfBuilder64::new(Endian::Little);
+    a.add_segment(&load);
+    a.add_segment(&load);
+
+    // The second program header goes past the end of the file.
+    let elf_a = ReadElf::from_slice(&a.buffer()[0..0x40 + 0x38 + 0x20]).unwrap();
+    let elf_b = ReadElf::from_slice(a.buffer()).unwrap();
+
+    let d = diff(&elf_a, &elf_b);
+    assert_eq!(d.old_segment_error, Some(ParseError::Truncated(1)));
+    assert_eq!(d.new_segment_error, None);
+    assert_eq!(
+        d.segments,
+        vec![SegmentChange::Added {
+            index: 1,
+            segment: load,
+        }]
+    );
+    assert!(!d.is_empty());
+}
@@ -0,0 +1,598 @@
+//! Test suite for formatting the ELF file as text.
+
+use readelf::*;
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder32, ElfBuilder64};
+use common::segment::segment;
+
+#[test]
+fn format_file_header_elf64() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_entry(0x401000);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    elf_builder.write_u16(0x34, 64); // e_ehsize
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let text = display::format_file_header(&elf);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "ELF Header:");
+    assert_eq!(
+        lines[1],
+        "  Magic:   7f 45 4c 46 02 01 01 00 00 00 00 00 00 00 00 00 "
+    );
+    assert_eq!(lines[2], "  Class:                             ELF64");
+    assert_eq!(
+        lines[3],
+        "  Data:                              2's complement, little endian"
+    );
+    assert_eq!(lines[4], "  Version:                           1 (current)");
+    assert_eq!(
+        lines[7],
+        "  Type:                              EXEC (Executable file)"
+    );
+    assert_eq!(lines[10], "  Entry point address:               0x401000");
+    assert_eq!(
+        lines[11],
+        "  Start of program headers:          64 (bytes into file)"
+    );
+    assert_eq!(lines[14], "  Size of this header:               64 (bytes)");
+    assert_eq!(lines[16], "  Number of program headers:         1");
+    assert_eq!(lines.len(), 20);
+}
+
+#[test]
+fn format_program_headers_elf32() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder.set_executable_type(ExecutableType::Executable);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let text = display::format_program_headers(&elf);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[1], "Elf file type is EXEC (Executable file)");
+    assert_eq!(
+        lines[3],
+        "There are 1 program headers, starting at offset 52"
+    );
+    assert_eq!(
+        lines[6],
+        "  Type           Offset   VirtAddr   PhysAddr   FileSiz MemSiz  Flg Align"
+    );
+    assert_eq!(
+        lines[7],
+        "  LOAD           0x001000 0x00401000 0x00401000 0x00180 0x00200 R E 0x1000"
+    );
+    assert_eq!(lines.len(), 8);
+}
+
+#[test]
+fn format_program_headers_elf64() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let text = display::format_program_headers(&elf);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[8],
+        "  LOAD           0x0000000000001000 0x0000000000401000 0x0000000000401000"
+    );
+    assert_eq!(
+        lines[9],
+        "                 0x0000000000000180 0x0000000000000200  RW     0x1000"
+    );
+}
+
+#[test]
+fn format_program_headers_none() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        display::format_program_headers(&elf),
+        "\nThere are no program headers in this file.\n"
+    );
+}
+
+#[test]
+fn segment_type_names() {
+    assert_eq!(display::segment_type(SegmentType::Load), "LOAD");
+    assert_eq!(
+        display::segment_type(SegmentType::from(0x6474E551)),
+        "GNU_STACK"
+    );
+    assert_eq!(
+        display::segment_type(SegmentType::from(0x60000010)),
+        "LOOS+0x10"
+    );
+    assert_eq!(
+        display::segment_type(SegmentType::from(0x70000001)),
+        "LOPROC+0x1"
+    );
+    assert_eq!(
+        display::segment_type(SegmentType::from(0x10)),
+        "<unknown>: 10"
+    );
+}
+
+#[test]
+fn segment_type_registered_names() {
+    let arm = Machine::from(Machine::ARM);
+    assert!(SegmentType::register_name(0x6000_0020, None, "VENDOR_INFO"));
+    assert!(SegmentType::register_name(
+        0x7000_0020,
+        Some(arm),
+        "ARM_VENDOR"
+    ));
+    assert_eq!(
+        display::segment_type(SegmentType::from(0x6000_0020)),
+        "VENDOR_INFO"
+    );
+    assert_eq!(
+        display::segment_type(SegmentType::from(0x7000_0020)),
+        "LOPROC+0x20"
+    );
+
+    // The program headers use the names registered for the machine.
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.set_machine(arm);
+    elf_builder.add_segment(&segment(
+        SegmentType::from(0x7000_0020),
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    let text = display::format_program_headers(&elf);
+    assert!(text
+        .lines()
+        .nth(7)
+        .unwrap()
+        .starts_with("  ARM_VENDOR     "));
+}
+
+#[test]
+fn segment_flags_names() {
+    assert_eq!(display::segment_flags(SegmentFlags::from(0)), "   ");
+    assert_eq!(
+        display::segment_flags(SegmentFlags::from(SegmentFlags::R | SegmentFlags::X)),
+        "R E"
+    );
+    assert_eq!(
+        display::segment_flags(SegmentFlags::from(0x10 | SegmentFlags::W)),
+        " W  0x10"
+    );
+}
+
+#[test]
+fn format_hex_dump() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.set_entry(0x41424344);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_hex_dump(&elf, 0, 26),
+        concat!(
+            "  0x00000000 7f454c46 01010100 00000000 00000000 .ELF............\n",
+            "  0x00000010 00000000 01000000 4443              ........DC\n"
+        )
+    );
+}
+
+#[test]
+fn format_hex_dump_end_of_file() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_vec(elf_builder.buffer()[0..0x38].to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_hex_dump(&elf, 0x30, 0x100),
+        "  0x00000030 00000000 00000000                   ........\n"
+    );
+    assert_eq!(display::format_hex_dump(&elf, 0x100, 0x10), "");
+    assert_eq!(display::format_hex_dump(&elf, u64::MAX, 0x10), "");
+}
+
+#[test]
+fn format_program_headers_elf64_wide() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let text = display::format_program_headers_wide(&elf);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[6],
+        "  Type           Offset   VirtAddr           PhysAddr           FileSiz  MemSiz   Flg Align"
+    );
+    assert_eq!(
+        lines[7],
+        "  LOAD           0x001000 0x0000000000401000 0x0000000000401000 0x000180 0x000200 RW  0x1000"
+    );
+    assert_eq!(lines.len(), 8);
+}
+
+#[test]
+fn format_program_headers_elf32_wide() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_program_headers_wide(&elf),
+        display::format_program_headers(&elf)
+    );
+}
+
+#[test]
+fn format_program_headers_color() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    elf_builder.add_segment(&ProgramHeader {
+        virtual_address: 0x402010,
+        ..segment(
+            SegmentType::Load,
+            SegmentFlags::R | SegmentFlags::W | SegmentFlags::X,
+            0x1000,
+            0x401000,
+            0x180,
+            0x200,
+        )
+    });
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let options = display::Options {
+        wide: false,
+        gnu: false,
+        color: true,
+    };
+    let text = display::format_program_headers_with(&elf, &options);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[5], "\x1b[1mProgram Headers:\x1b[0m");
+    assert_eq!(
+        lines[7],
+        "  LOAD           0x001000 0x00401000 0x00401000 0x00180 0x00200 \x1b[32mR\x1b[0m \x1b[36mE\x1b[0m 0x1000"
+    );
+    assert_eq!(
+        lines[8],
+        "  LOAD           0x001000 0x00402010 0x00401000 0x00180 0x00200 \x1b[31mRWE\x1b[0m \x1b[31m0x1000\x1b[0m"
+    );
+}
+
+#[test]
+fn format_without_color() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let options = display::Options::default();
+    assert_eq!(
+        display::format_file_header_with(&elf, &options),
+        display::format_file_header(&elf)
+    );
+    assert_eq!(
+        display::format_program_headers_with(&elf, &options),
+        display::format_program_headers(&elf)
+    );
+}
+
+#[test]
+fn format_diff() {
+    let mut a_builder = ElfBuilder32::new(Endian::Little);
+    a_builder.set_entry(0x1000);
+    a_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let a = ReadElf::from_vec(a_builder.buffer().to_vec()).unwrap();
+
+    let mut b_builder = ElfBuilder32::new(Endian::Little);
+    b_builder.set_entry(0x2000);
+    b_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x401000,
+        0x280,
+        0x200,
+    ));
+    b_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let b = ReadElf::from_vec(b_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_diff(&diff(&a, &b)),
+        concat!(
+            "ELF Header:\n",
+            "  Entry point address:               0x1000 -> 0x2000\n",
+            "  Number of program headers:         1 -> 2\n",
+            "Program Headers:\n",
+            "  ~ [0 -> 0] LOAD FileSiz 0x180 -> 0x280, MemSiz 0x200 -> 0x200\n",
+            "  + [1] LOAD Offset 0x1000, VirtAddr 0x401000, FileSiz 0x180, MemSiz 0x200, Flg RW\n",
+        )
+    );
+}
+
+#[test]
+fn format_diff_segment_fields() {
+    let load = segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    );
+    let mut a_builder = ElfBuilder64::new(Endian::Little);
+    a_builder.add_segment(&load);
+    a_builder.add_segment(&load);
+    let mut b_builder = ElfBuilder64::new(Endian::Little);
+    b_builder.add_segment(&ProgramHeader {
+        physical_address: 0x1000,
+        alignment: 0x10000,
+        ..load
+    });
+    b_builder.add_segment(&load);
+
+    // The second program header of the first file goes past the end of the
+    // file.
+    let a = ReadElf::from_slice(&a_builder.buffer()[0..0x40 + 0x38 + 0x20]).unwrap();
+    let b = ReadElf::from_slice(b_builder.buffer()).unwrap();
+
+    assert_eq!(
+        display::format_diff(&diff(&a, &b)),
+        concat!(
+            "Program Headers:\n",
+            "  ~ [0 -> 0] LOAD PhysAddr 0x401000 -> 0x1000\n",
+            "  ~ [0 -> 0] LOAD Align 0x1000 -> 0x10000\n",
+            "  + [1] LOAD Offset 0x1000, VirtAddr 0x401000, FileSiz 0x180, MemSiz 0x200, Flg R\n",
+            "Program headers of the first file: Entry 1 is truncated\n",
+        )
+    );
+}
+
+#[test]
+fn format_diff_empty() {
+    let elf_builder = ElfBuilder64::new(Endian::Big);
+    let a = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let b = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(display::format_diff(&diff(&a, &b)), "");
+}
+
+#[test]
+fn format_strings() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    for (i, b) in b"abc\0hello world\x01\x02four".iter().enumerate() {
+        elf_builder.write_u8(0x100 + i, *b);
+    }
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_strings(&elf, 0x100, 22, 4),
+        concat!("  [   104]  hello world\n", "  [   111]  four\n")
+    );
+    assert_eq!(
+        display::format_strings(&elf, 0x100, 3, 3),
+        "  [   100]  abc\n"
+    );
+    assert_eq!(display::format_strings(&elf, 0x100, 0, 1), "");
+}
+
+#[test]
+fn format_strings_end_of_file() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    for (i, b) in b"tail".iter().enumerate() {
+        elf_builder.write_u8(0x40 + i, *b);
+    }
+    let elf = ReadElf::from_vec(elf_builder.buffer()[0..0x44].to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_strings(&elf, 0x40, 0x1000, 4),
+        "  [    40]  tail\n"
+    );
+}
+
+#[test]
+fn format_file_header_gnu() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_os_abi(OsAbi::from(OsAbi::LINUX))
+        .set_machine(Machine::from(Machine::X86_64));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let text = display::format_file_header(&elf);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[5], "  OS/ABI:                            Linux");
+    assert_eq!(lines[8], "  Machine:                           AMD x86-64");
+
+    let options = display::Options {
+        gnu: true,
+        ..Default::default()
+    };
+    let text = display::format_file_header_with(&elf, &options);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[5], "  OS/ABI:                            UNIX - GNU");
+    assert_eq!(
+        lines[8],
+        "  Machine:                           Advanced Micro Devices X86-64"
+    );
+}
+
+#[test]
+fn format_file_header_pie() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_executable_type(ExecutableType::Dynamic);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Dynamic,
+        flags: SegmentFlags::from(SegmentFlags::R | SegmentFlags::W),
+        file_offset: 0x800,
+        virtual_address: 0x800,
+        physical_address: 0x800,
+        file_size: 0x30,
+        memory_size: 0x30,
+        alignment: 0x8,
+    });
+    elf_builder.write_u64(0x800, 0x1e); // DT_FLAGS
+    elf_builder.write_u64(0x808, 0x8);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    let text = display::format_file_header(&elf);
+    assert_eq!(
+        text.lines().nth(7).unwrap(),
+        "  Type:                              DYN (Shared object file)"
+    );
+
+    elf_builder.write_u64(0x810, 0x6ffffffb); // DT_FLAGS_1
+    elf_builder.write_u64(0x818, 0x08000001); // DF_1_PIE | DF_1_NOW
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    let text = display::format_file_header(&elf);
+    assert_eq!(
+        text.lines().nth(7).unwrap(),
+        "  Type:                              DYN (Position-Independent Executable file)"
+    );
+}
+
+#[test]
+fn format_summary() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_machine(Machine::from(Machine::PPC));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(
+        display::format_summary(&elf, "bash", &display::Options::default()),
+        "bash: ELF32, big endian, PowerPC, EXEC (Executable file)"
+    );
+
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Interpreter,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x800,
+        virtual_address: 0x800,
+        physical_address: 0x800,
+        file_size: 0x0D,
+        memory_size: 0x0D,
+        alignment: 1,
+    });
+    for (i, b) in b"/lib/ld.so.1\0".iter().enumerate() {
+        elf_builder.write_u8(0x800 + i, *b);
+    }
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(
+        display::format_summary(&elf, "bash", &display::Options::default()),
+        "bash: ELF32, big endian, PowerPC, EXEC (Executable file), interpreter /lib/ld.so.1"
+    );
+}
+
+#[test]
+fn format_template() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_machine(Machine::from(Machine::X86_64))
+        .set_entry(0x401000);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x180,
+        0x200,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        display::format_template(&elf, "a.out", "%{file}\t%{machine} %{entry}\\n").unwrap(),
+        "a.out\tAMD x86-64 0x401000\n"
+    );
+    assert_eq!(
+        display::format_template(&elf, "a.out", "%{class}/%{phnum}/%{interpreter}/100%%").unwrap(),
+        "ELF64/1//100%"
+    );
+    assert_eq!(
+        display::format_template(&elf, "a.out", "%d \\\\ %").unwrap(),
+        "%d \\ %"
+    );
+}
+
+#[test]
+fn format_template_unknown_field() {
+    let elf_builder = ElfBuilder64::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        display::format_template(&elf, "a.out", "%{machine} %{soname}"),
+        Err(display::TemplateError::UnknownField("soname".to_string()))
+    );
+    assert_eq!(
+        display::format_template(&elf, "a.out", "%{machine"),
+        Err(display::TemplateError::Unterminated("machine".to_string()))
+    );
+    assert_eq!(
+        display::TemplateError::UnknownField("soname".to_string()).to_string(),
+        "unknown field %{soname}"
+    );
+}
@@ -9,6 +9,7 @@ use std::io::BufReader;
 mod common;
 use common::builder::{ElfBuilder, ElfBuilder32, ElfBuilder64};
 use common::config::{self, ElfHeaders};
+use common::segment::segment;
 
 #[test]
 fn read_elf_headers() {
@@ -174,6 +175,54 @@ fn elf_header_invalid_version_both() {
     }
 }
 
+#[test]
+fn elf_header_version_not_strict() {
+    let options = ReadOptions {
+        strict_version: false,
+        ..Default::default()
+    };
+
+    let mut buff = get_header_64();
+    buff[20] = 2;
+    assert!(ReadElf::from_slice_with_options(&buff, options).is_ok());
+    let elf_file = ReadElf::from_vec_with_options(buff.clone(), options).unwrap();
+    assert_eq!(elf_file.version, 2);
+    assert_eq!(elf_file.options(), options);
+
+    let elf_file = ReadElf::from_vec_with_options(buff.clone(), ReadOptions::default());
+    assert_eq!(elf_file.unwrap_err(), ParseError::NotElf);
+
+    // The version of the identification is always checked.
+    buff[6] = 0;
+    let elf_file = ReadElf::from_vec_with_options(buff, options);
+    assert_eq!(elf_file.unwrap_err(), ParseError::NotElf);
+}
+
+#[test]
+fn elf_header_with_options_limit() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Null,
+        flags: SegmentFlags::from(SegmentFlags::NONE),
+        file_offset: 0,
+        virtual_address: 0,
+        physical_address: 0,
+        file_size: 0,
+        memory_size: 0,
+        alignment: 0,
+    });
+    let options = ReadOptions {
+        max_program_headers: 0,
+        ..Default::default()
+    };
+    let buffer: std::sync::Arc<[u8]> = std::sync::Arc::from(elf_builder.buffer());
+    assert!(ReadElf::from_arc_with_options(buffer.clone(), ReadOptions::default()).is_ok());
+    assert_eq!(
+        ReadElf::from_arc_with_options(buffer, options).unwrap_err(),
+        ParseError::ProgramHeaderLimit(1)
+    );
+}
+
 #[test]
 fn elf_header_all_osabi() {
     for i in 0..=255_u8 {
@@ -713,3 +762,1649 @@ fn segments_indexed() {
     assert_eq!(segments[1], elf.program_headers().index(1).unwrap());
     assert!(elf.program_headers().index(2).is_none());
 }
+
+#[test]
+fn segments_checked_phoff_umax() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Big);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Null,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x1000,
+        virtual_address: 0x2000,
+        physical_address: 0x3000,
+        file_size: 0x4000,
+        memory_size: 0x5000,
+        alignment: 0x10,
+    });
+
+    elf_builder.write_u64(0x20, u64::MAX);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let segments: Vec<Result<ProgramHeader, ParseError>> =
+        elf.program_headers().iter_checked().collect();
+    assert_eq!(segments, vec![Err(ParseError::Overflow(0))]);
+}
+
+#[test]
+fn segments_checked_truncated() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Big);
+    for _ in 0..2 {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: SegmentType::Load,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0x1000,
+            virtual_address: 0x2000,
+            physical_address: 0x3000,
+            file_size: 0x4000,
+            memory_size: 0x5000,
+            alignment: 0x10,
+        });
+    }
+
+    // The second program header goes past the end of the file.
+    let buffer = &elf_builder.buffer()[0..0x40 + 0x38 + 0x20];
+    let elf = ReadElf::from_slice(buffer).unwrap();
+
+    let mut segments = elf.program_headers();
+    assert!(segments.try_next().unwrap().is_ok());
+    assert_eq!(segments.try_next(), Some(Err(ParseError::Truncated(1))));
+    assert_eq!(segments.try_next(), None);
+}
+
+#[test]
+fn segments_checked_phentsize_too_small() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Null,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x1000,
+        virtual_address: 0x2000,
+        physical_address: 0x3000,
+        file_size: 0x4000,
+        memory_size: 0x5000,
+        alignment: 0x10,
+    });
+
+    // Set `e_phentsize` to one less than the size of the structure.
+    elf_builder.write_u16(0x2A, 31);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let segments: Vec<Result<ProgramHeader, ParseError>> =
+        elf.program_headers().iter_checked().collect();
+    assert_eq!(segments, vec![Err(ParseError::EntrySize(31))]);
+    assert!(elf.program_headers().index(0).is_none());
+}
+
+#[test]
+fn segments_reverse() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    for i in 0..3 {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: SegmentType::Load,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0x1000 * i,
+            virtual_address: 0x1000 * i,
+            physical_address: 0,
+            file_size: 0x100,
+            memory_size: 0x100,
+            alignment: 0x1000,
+        });
+    }
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let mut segments = elf.program_headers();
+    assert_eq!(segments.size_hint(), (0, Some(3)));
+    assert_eq!(segments.next().unwrap().file_offset, 0x0000);
+    assert_eq!(segments.remaining(), 2);
+    assert_eq!(segments.next_back().unwrap().file_offset, 0x2000);
+    assert_eq!(segments.remaining(), 1);
+    assert_eq!(segments.next_back().unwrap().file_offset, 0x1000);
+    assert_eq!(segments.remaining(), 0);
+    assert_eq!(segments.len(), 3);
+    assert!(segments.next().is_none());
+    assert!(segments.next_back().is_none());
+
+    let offsets: Vec<u64> = elf.program_headers().rev().map(|s| s.file_offset).collect();
+    assert_eq!(offsets, vec![0x2000, 0x1000, 0x0000]);
+}
+
+#[test]
+fn segments_reverse_truncated() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Big);
+    for _ in 0..2 {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: SegmentType::Load,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0x1000,
+            virtual_address: 0x2000,
+            physical_address: 0x3000,
+            file_size: 0x4000,
+            memory_size: 0x5000,
+            alignment: 0x10,
+        });
+    }
+
+    // The second program header goes past the end of the file.
+    let buffer = &elf_builder.buffer()[0..0x40 + 0x38 + 0x20];
+    let elf = ReadElf::from_slice(buffer).unwrap();
+
+    let mut segments = elf.program_headers().iter_checked();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments.next_back(), Some(Err(ParseError::Truncated(1))));
+    assert!(segments.next().unwrap().is_ok());
+    assert_eq!(segments.next(), None);
+
+    // Iterating from the front stops at the same entry, without the reason.
+    let mut segments = elf.program_headers();
+    assert_eq!(segments.size_hint(), (0, Some(2)));
+    assert!(segments.next().is_some());
+    assert!(segments.next().is_none());
+}
+
+#[test]
+fn segments_reiterable() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    for i in 0..2 {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: SegmentType::Load,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0x1000 * i,
+            virtual_address: 0x1000 * i,
+            physical_address: 0,
+            file_size: 0x100,
+            memory_size: 0x100,
+            alignment: 0x1000,
+        });
+    }
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let segments = elf.segments();
+    assert_eq!(segments.len(), 2);
+    assert!(!segments.is_empty());
+    let mut count = 0;
+    for _ in &segments {
+        count += 1;
+    }
+    for _ in segments {
+        count += 1;
+    }
+    assert_eq!(count, 4);
+    assert_eq!(segments.get(1).unwrap().file_offset, 0x1000);
+    assert!(segments.get(2).is_none());
+
+    // Iterating by reference doesn't advance the iterator.
+    let mut headers = elf.program_headers();
+    assert_eq!((&headers).into_iter().count(), 2);
+    for _ in headers.by_ref().take(1) {}
+    assert_eq!(headers.remaining(), 1);
+    assert_eq!((&headers).into_iter().count(), 1);
+}
+
+#[test]
+fn segments_of_type() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    for (i, t) in [SegmentType::Load, SegmentType::Note, SegmentType::Load]
+        .into_iter()
+        .enumerate()
+    {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: t,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0x1000 * i as u64,
+            virtual_address: 0x1000 * i as u64,
+            physical_address: 0,
+            file_size: 0x100,
+            memory_size: 0x100,
+            alignment: 0x1000,
+        });
+    }
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let loads: Vec<u64> = elf
+        .segments_of_type(SegmentType::Load)
+        .map(|s| s.file_offset)
+        .collect();
+    assert_eq!(loads, vec![0x0000, 0x2000]);
+    assert_eq!(elf.segments_of_type(SegmentType::Unknown(4)).count(), 1);
+    assert_eq!(elf.segments_of_type(SegmentType::Dynamic).count(), 0);
+}
+
+#[test]
+fn segments_indexed_iterator() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    for i in 0..3 {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: SegmentType::Load,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0x1000 * i,
+            virtual_address: 0x1000 * i,
+            physical_address: 0,
+            file_size: 0x100,
+            memory_size: 0x100,
+            alignment: 0x1000,
+        });
+    }
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let mut segments = elf.program_headers();
+    segments.next();
+    let indexed: Vec<(usize, u64)> = segments
+        .indexed()
+        .map(|(i, s)| (i, s.file_offset))
+        .collect();
+    assert_eq!(indexed, vec![(1, 0x1000), (2, 0x2000)]);
+
+    let reversed: Vec<usize> = elf
+        .program_headers()
+        .indexed()
+        .rev()
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(reversed, vec![2, 1, 0]);
+}
+
+#[test]
+fn string_section_index() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.write_u16(0x3E, 29);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.string_section_index(), Some(29));
+
+    elf_builder.write_u16(0x3E, 0);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.string_section_index(), None);
+}
+
+#[test]
+fn string_section_index_extended_elf32() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder.write_u32(0x20, 0x400); // e_shoff
+    elf_builder.write_u16(0x2E, 40); // e_shentsize
+    elf_builder.write_u16(0x32, 0xFFFF); // e_shstrndx = SHN_XINDEX
+    elf_builder.write_u32(0x400 + 24, 0x12345); // sh_link of section 0
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.string_section_index(), Some(0x12345));
+}
+
+#[test]
+fn string_section_index_extended_elf64() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.write_u64(0x28, 0x400); // e_shoff
+    elf_builder.write_u16(0x3A, 64); // e_shentsize
+    elf_builder.write_u16(0x3E, 0xFFFF); // e_shstrndx = SHN_XINDEX
+    elf_builder.write_u32(0x400 + 40, 0x12345); // sh_link of section 0
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.string_section_index(), Some(0x12345));
+
+    // The section header is too small to contain `sh_link`.
+    elf_builder.write_u16(0x3A, 63);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.string_section_index(), None);
+
+    // The section header is outside of the file.
+    elf_builder.write_u16(0x3A, 64);
+    elf_builder.write_u64(0x28, u64::MAX - 8);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.string_section_index(), None);
+}
+
+#[test]
+fn header_ranges() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    for _ in 0..2 {
+        elf_builder.add_segment(&ProgramHeader {
+            segment_type: SegmentType::Load,
+            flags: SegmentFlags::from(SegmentFlags::R),
+            file_offset: 0,
+            virtual_address: 0,
+            physical_address: 0,
+            file_size: 0x100,
+            memory_size: 0x100,
+            alignment: 0x1000,
+        });
+    }
+    elf_builder.write_u16(0x34, 64); // e_ehsize
+    elf_builder.write_u64(0x28, 0x400); // e_shoff
+    elf_builder.write_u16(0x3A, 64); // e_shentsize
+    elf_builder.write_u16(0x3C, 3); // e_shnum
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(elf.file_header_range(), 0..64);
+    assert_eq!(elf.program_header_range(), Some(0x40..0x40 + 2 * 0x38));
+    assert_eq!(elf.section_header_range(), Some(0x400..0x400 + 3 * 64));
+}
+
+#[test]
+fn header_ranges_none() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.program_header_range(), None);
+    assert_eq!(elf.section_header_range(), None);
+}
+
+#[test]
+fn header_ranges_extended() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder.write_u32(0x1C, 0x34); // e_phoff
+    elf_builder.write_u16(0x2A, 32); // e_phentsize
+    elf_builder.write_u16(0x2C, 0xFFFF); // e_phnum = PN_XNUM
+    elf_builder.write_u32(0x20, 0x400); // e_shoff
+    elf_builder.write_u16(0x2E, 40); // e_shentsize
+    elf_builder.write_u16(0x30, 0); // e_shnum
+    elf_builder.write_u32(0x400 + 20, 0x10000); // sh_size of section 0
+    elf_builder.write_u32(0x400 + 28, 0x10001); // sh_info of section 0
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.program_header_range(), Some(0x34..0x34 + 0x10001 * 32));
+    assert_eq!(
+        elf.section_header_range(),
+        Some(0x400..0x400 + 0x10000 * 40)
+    );
+
+    // The iterator uses the same number of program headers.
+    assert_eq!(elf.program_headers().len(), 0x10001);
+    assert_eq!(elf.segments().len(), 0x10001);
+    assert!(elf.program_headers().index(0).is_some());
+    assert!(elf.program_headers().index(0x10001).is_none());
+
+    let options = ReadOptions {
+        max_program_headers: 0x10000,
+        ..ReadOptions::default()
+    };
+    assert_eq!(
+        elf.try_clone().unwrap().with_options(options).unwrap_err(),
+        ParseError::ProgramHeaderLimit(0x10001)
+    );
+}
+
+#[test]
+fn header_ranges_overflow() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.write_u64(0x20, u64::MAX - 8); // e_phoff
+    elf_builder.write_u16(0x36, 56); // e_phentsize
+    elf_builder.write_u16(0x38, 1); // e_phnum
+    elf_builder.write_u64(0x28, u64::MAX); // e_shoff
+    elf_builder.write_u16(0x3A, 64); // e_shentsize
+    elf_builder.write_u16(0x3C, 1); // e_shnum
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.program_header_range(), None);
+    assert_eq!(elf.section_header_range(), None);
+}
+
+#[test]
+fn interpreter() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Interpreter,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        28,
+        28,
+    ));
+    for (i, b) in b"/lib64/ld-linux-x86-64.so.2\0".iter().enumerate() {
+        elf_builder.write_u8(0x800 + i, *b);
+    }
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        elf.interpreter().as_deref(),
+        Some("/lib64/ld-linux-x86-64.so.2")
+    );
+}
+
+#[test]
+fn interpreter_not_terminated() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder.add_segment(&segment(
+        SegmentType::Interpreter,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        4,
+        4,
+    ));
+    for (i, b) in b"/lib/ld.so".iter().enumerate() {
+        elf_builder.write_u8(0x800 + i, *b);
+    }
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(elf.interpreter().as_deref(), Some("/lib"));
+}
+
+#[test]
+fn interpreter_none() {
+    let elf_builder = ElfBuilder32::new(Endian::Big);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.interpreter(), None);
+
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder.add_segment(&segment(
+        SegmentType::Interpreter,
+        SegmentFlags::R,
+        0x10000,
+        0x800,
+        16,
+        16,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.interpreter(), None);
+}
+
+#[test]
+fn from_arc_threads() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_entry(0x401000);
+    let buffer: std::sync::Arc<[u8]> = std::sync::Arc::from(elf_builder.buffer());
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let buffer = buffer.clone();
+            std::thread::spawn(move || ReadElf::from_arc(buffer).unwrap().entry)
+        })
+        .collect();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), 0x401000);
+    }
+}
+
+#[test]
+fn from_arc_not_elf() {
+    let buffer: std::sync::Arc<[u8]> = std::sync::Arc::from(&b"\x7fELX"[..]);
+    assert!(ReadElf::from_arc(buffer).is_none());
+}
+
+#[test]
+fn try_clone_slice() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_entry(0x401000);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let clone = elf.try_clone().unwrap();
+    drop(elf);
+    assert_eq!(clone.entry, 0x401000);
+    assert_eq!(
+        clone.raw_header(),
+        ReadElf::from_slice(elf_builder.buffer())
+            .unwrap()
+            .raw_header()
+    );
+}
+
+#[test]
+fn try_clone_vec_threads() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    elf_builder.set_entry(0x1000);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let clone = elf.try_clone().unwrap();
+            std::thread::spawn(move || (clone.entry, clone.program_headers().len()))
+        })
+        .collect();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), (0x1000, 0));
+    }
+}
+
+#[test]
+fn try_clone_options() {
+    let elf_builder = ElfBuilder64::new(Endian::Little);
+    let options = ReadOptions {
+        max_allocation: 16,
+        ..Default::default()
+    };
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec())
+        .unwrap()
+        .with_options(options)
+        .unwrap();
+    assert_eq!(elf.try_clone().unwrap().options(), options);
+}
+
+#[test]
+fn open_with_options_error() {
+    let path = config::test_resource_path("elf/does-not-exist");
+    let err = ReadElf::open_with_options(path, ReadOptions::default()).unwrap_err();
+    assert_eq!(err, ParseError::Io(std::io::ErrorKind::NotFound));
+}
+
+#[test]
+fn try_clone_file() {
+    let path = config::test_resource_path("elf/debian-2.1-m68k/plot");
+    let elf = ReadElf::open_with_options(path, ReadOptions::default()).unwrap();
+    let clone = elf.try_clone().unwrap();
+    assert_eq!(clone.machine, elf.machine);
+    assert_eq!(clone.program_headers().len(), elf.program_headers().len());
+}
+
+#[test]
+fn segment_predicates() {
+    let mut ph = ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R | SegmentFlags::X),
+        file_offset: 0,
+        virtual_address: 0,
+        physical_address: 0,
+        file_size: 0,
+        memory_size: 0,
+        alignment: 0,
+    };
+    assert!(ph.is_loadable());
+    assert!(!ph.is_dynamic());
+    assert!(ph.flags.is_executable());
+
+    ph.segment_type = SegmentType::Unknown(1);
+    assert!(ph.is_loadable());
+
+    for (segment_type, check) in [
+        (
+            SegmentType::Dynamic,
+            ProgramHeader::is_dynamic as fn(&ProgramHeader) -> bool,
+        ),
+        (SegmentType::Interpreter, ProgramHeader::is_interpreter),
+        (SegmentType::Note, ProgramHeader::is_note),
+        (SegmentType::ThreadLocalStorage, ProgramHeader::is_tls),
+    ] {
+        ph.segment_type = segment_type;
+        assert!(check(&ph));
+        assert!(!ph.is_loadable());
+    }
+}
+
+#[test]
+fn segment_ranges() {
+    let ph = ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x1000,
+        virtual_address: 0x401000,
+        physical_address: 0,
+        file_size: 0x200,
+        memory_size: 0x300,
+        alignment: 0x1000,
+    };
+    assert_eq!(ph.file_range(), Some(0x1000..0x1200));
+    assert_eq!(ph.vaddr_range(), Some(0x401000..0x401300));
+    assert_eq!(ph.end_vaddr(), Some(0x401300));
+    assert!(ph.contains_offset(0x1000));
+    assert!(ph.contains_offset(0x11FF));
+    assert!(!ph.contains_offset(0x1200));
+    assert!(!ph.contains_offset(0xFFF));
+    assert!(ph.contains_vaddr(0x401000));
+    assert!(ph.contains_vaddr(0x4012FF));
+    assert!(!ph.contains_vaddr(0x401300));
+}
+
+#[test]
+fn segment_ranges_empty() {
+    let ph = ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x1000,
+        virtual_address: 0x401000,
+        physical_address: 0,
+        file_size: 0,
+        memory_size: 0,
+        alignment: 0x1000,
+    };
+    assert_eq!(ph.file_range(), Some(0x1000..0x1000));
+    assert!(!ph.contains_offset(0x1000));
+    assert!(!ph.contains_vaddr(0x401000));
+}
+
+#[test]
+fn segment_ranges_overflow() {
+    let ph = ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: u64::MAX - 0xFF,
+        virtual_address: u64::MAX - 0xFF,
+        physical_address: 0,
+        file_size: 0xFF,
+        memory_size: 0x100,
+        alignment: 0,
+    };
+    assert_eq!(ph.file_range(), Some(u64::MAX - 0xFF..u64::MAX));
+    assert_eq!(ph.end_vaddr(), None);
+    assert_eq!(ph.vaddr_range(), None);
+    assert!(ph.contains_offset(u64::MAX - 1));
+    assert!(!ph.contains_vaddr(u64::MAX - 1));
+}
+
+fn arm_exidx_builder(machine: u16) -> ElfBuilder32 {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.set_machine(Machine::from(machine));
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::from(0x70000001),
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0x800,
+        virtual_address: 0x10800,
+        physical_address: 0x10800,
+        file_size: 24,
+        memory_size: 24,
+        alignment: 4,
+    });
+    let words = [
+        0x7FFFF800, 0x00000001, // 0x10000, EXIDX_CANTUNWIND
+        0x7FFFF8F8, 0x80B0B0B0, // 0x10100, inline
+        0x7FFFF9F0, 0x000000EC, // 0x10200, .ARM.extab at 0x10900
+    ];
+    for (i, w) in words.iter().enumerate() {
+        elf_builder.write_u32(0x800 + i * 4, *w);
+    }
+    elf_builder
+}
+
+#[test]
+fn arm_exception_index() {
+    let elf_builder = arm_exidx_builder(Machine::ARM);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        elf.arm_exception_index(),
+        Some(vec![
+            ArmExceptionIndex {
+                function: 0x10000,
+                unwind: ArmUnwind::CantUnwind,
+            },
+            ArmExceptionIndex {
+                function: 0x10100,
+                unwind: ArmUnwind::Inline(0x80B0B0B0),
+            },
+            ArmExceptionIndex {
+                function: 0x10200,
+                unwind: ArmUnwind::Table(0x10900),
+            },
+        ])
+    );
+}
+
+#[test]
+fn arm_exception_index_other_machine() {
+    let elf_builder = arm_exidx_builder(Machine::MIPS);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.arm_exception_index(), None);
+}
+
+#[test]
+fn arm_exception_index_limit() {
+    let elf_builder = arm_exidx_builder(Machine::ARM);
+    let elf = ReadElf::from_slice(elf_builder.buffer())
+        .unwrap()
+        .with_options(ReadOptions {
+            max_allocation: 16,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(elf.arm_exception_index(), None);
+}
+
+fn signed_module(signer: &[u8], key_id: &[u8], id_type: u8) -> Vec<u8> {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_executable_type(ExecutableType::Relocatable);
+    let mut module = elf_builder.buffer().to_vec();
+    let signature = [0x30, 0x82, 0x01, 0x02, 0xAA];
+    module.extend_from_slice(signer);
+    module.extend_from_slice(key_id);
+    module.extend_from_slice(&signature);
+    module.extend_from_slice(&[0, 0, id_type, signer.len() as u8, key_id.len() as u8]);
+    module.extend_from_slice(&[0, 0, 0]);
+    module.extend_from_slice(&(signature.len() as u32).to_be_bytes());
+    module.extend_from_slice(b"~Module signature appended~\n");
+    module
+}
+
+#[test]
+fn module_signature_pkcs7() {
+    let module = signed_module(b"", b"", ModuleSignature::PKEY_ID_PKCS7);
+    let size = module.len() as u64;
+    let elf = ReadElf::from_vec(module).unwrap();
+
+    let sig = elf.module_signature().unwrap();
+    assert_eq!(sig.id_type, ModuleSignature::PKEY_ID_PKCS7);
+    assert_eq!(sig.algorithm, 0);
+    assert_eq!(sig.hash, 0);
+    assert!(sig.signer.is_empty());
+    assert!(sig.key_id.is_empty());
+    assert_eq!(sig.module_size, 0x2000);
+    assert_eq!(sig.signature, 0x2000..0x2005);
+    assert_eq!(size, 0x2005 + 12 + 28);
+}
+
+#[test]
+fn module_signature_signer() {
+    let module = signed_module(b"Build key", b"\x01\x02", ModuleSignature::PKEY_ID_X509);
+    let elf = ReadElf::from_vec(module).unwrap();
+
+    let sig = elf.module_signature().unwrap();
+    assert_eq!(sig.id_type, ModuleSignature::PKEY_ID_X509);
+    assert_eq!(sig.signer, b"Build key");
+    assert_eq!(sig.key_id, b"\x01\x02");
+    assert_eq!(sig.module_size, 0x2000);
+    assert_eq!(sig.signature, 0x200B..0x2010);
+}
+
+#[test]
+fn module_signature_unsigned() {
+    let elf_builder = ElfBuilder64::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.module_signature(), None);
+}
+
+#[test]
+fn module_signature_corrupt() {
+    let mut module = signed_module(b"", b"", ModuleSignature::PKEY_ID_PKCS7);
+    let info = module.len() - 28 - 4;
+    module[info..info + 4].copy_from_slice(&0x10000_u32.to_be_bytes());
+    let elf = ReadElf::from_vec(module).unwrap();
+    assert_eq!(elf.module_signature(), None);
+}
+
+fn go_build_info_builder(modinfo: &str) -> ElfBuilder64 {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R | SegmentFlags::W),
+        file_offset: 0x800,
+        virtual_address: 0x10800,
+        physical_address: 0x10800,
+        file_size: 0x800,
+        memory_size: 0x800,
+        alignment: 0x1000,
+    });
+
+    let mut blob = b"\xff Go buildinf:\x08\x02".to_vec();
+    blob.resize(32, 0);
+    let version = b"go1.22.3";
+    blob.push(version.len() as u8);
+    blob.extend_from_slice(version);
+    let modinfo = format!("{:16}{}{:16}", "", modinfo, "");
+    blob.push(0x80 | (modinfo.len() & 0x7F) as u8);
+    blob.push((modinfo.len() >> 7) as u8);
+    blob.extend_from_slice(modinfo.as_bytes());
+
+    // The build information is after some other data, aligned to 16 bytes.
+    for (i, b) in blob.iter().enumerate() {
+        elf_builder.write_u8(0x830 + i, *b);
+    }
+    elf_builder
+}
+
+#[test]
+fn go_build_info() {
+    let modinfo = "path\texample.com/tool\nmod\texample.com/tool\tv1.2.3\th1:xyz=\n\
+                   dep\tgolang.org/x/sys\tv0.20.0\th1:abc=\nbuild\tGOOS=linux\n";
+    let elf_builder = go_build_info_builder(modinfo);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let info = elf.go_build_info().unwrap();
+    assert_eq!(info.go_version, "go1.22.3");
+    assert_eq!(info.path, "example.com/tool");
+    assert_eq!(
+        info.main,
+        Some(GoModule {
+            path: "example.com/tool".to_string(),
+            version: "v1.2.3".to_string(),
+            sum: "h1:xyz=".to_string(),
+            replace: None,
+        })
+    );
+    assert_eq!(info.deps.len(), 1);
+    assert_eq!(info.deps[0].path, "golang.org/x/sys");
+    assert_eq!(
+        info.settings,
+        vec![("GOOS".to_string(), "linux".to_string())]
+    );
+}
+
+#[test]
+fn go_build_info_none() {
+    let elf_builder = ElfBuilder64::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.go_build_info(), None);
+
+    // Go older than 1.18 has pointers to the strings.
+    let mut elf_builder = go_build_info_builder("path\texample.com/tool\n");
+    elf_builder.write_u8(0x830 + 15, 0);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.go_build_info(), None);
+
+    // The length of the version, 1 << 36, is longer than the file.
+    let mut elf_builder = go_build_info_builder("path\texample.com/tool\n");
+    for (i, b) in [0x80, 0x80, 0x80, 0x80, 0x80, 0x02].iter().enumerate() {
+        elf_builder.write_u8(0x830 + 32 + i, *b);
+    }
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.go_build_info(), None);
+}
+
+/// Write a note at the offset, and return the offset of the next note.
+fn write_note<'b, B: ElfBuilder<'b>>(
+    elf_builder: &mut B,
+    offset: usize,
+    name: &[u8],
+    note_type: u32,
+    desc: &[u8],
+) -> usize {
+    elf_builder.write_u32(offset, name.len() as u32);
+    elf_builder.write_u32(offset + 4, desc.len() as u32);
+    elf_builder.write_u32(offset + 8, note_type);
+    for (i, b) in name.iter().enumerate() {
+        elf_builder.write_u8(offset + 12 + i, *b);
+    }
+    let desc_offset = (offset + 12 + name.len() + 3) & !3;
+    for (i, b) in desc.iter().enumerate() {
+        elf_builder.write_u8(desc_offset + i, *b);
+    }
+    (desc_offset + desc.len() + 3) & !3
+}
+
+#[test]
+fn notes() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    let next = write_note(&mut elf_builder, 0x800, b"GNU\0", 3, &[0xAB; 20]);
+    let end = write_note(&mut elf_builder, next, b"Go\0", 4, b"abc");
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        elf.notes(),
+        vec![
+            Note {
+                name: "GNU".to_string(),
+                note_type: 3,
+                desc: vec![0xAB; 20],
+            },
+            Note {
+                name: "Go".to_string(),
+                note_type: 4,
+                desc: b"abc".to_vec(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn notes_truncated() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let next = write_note(&mut elf_builder, 0x800, b"GNU\0", 3, &[0xAB; 20]);
+    let end = write_note(&mut elf_builder, next, b"GNU\0", 1, &[0; 16]);
+    let size = (end - 0x800 - 4) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let notes = elf.notes();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].note_type, 3);
+}
+
+#[test]
+fn notes_limit() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let end = write_note(&mut elf_builder, 0x800, b"GNU\0", 3, &[0xAB; 20]);
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer())
+        .unwrap()
+        .with_options(ReadOptions {
+            max_allocation: 16,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(elf.notes(), vec![]);
+}
+
+#[test]
+fn package_metadata() {
+    let json = br#"{"type":"rpm","name":"bash","version":"5.2.26-3.fc40","architecture":"x86_64","osCpe":"cpe:/o:fedoraproject:fedora:40"}"#;
+    let mut desc = json.to_vec();
+    desc.push(0);
+
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let end = write_note(&mut elf_builder, 0x800, b"FDO\0", 0xCAFE1A7E, &desc);
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let metadata = elf.package_metadata().unwrap();
+    assert_eq!(metadata.json.as_bytes(), json);
+    #[cfg(feature = "serde")]
+    {
+        assert_eq!(metadata.package_type.as_deref(), Some("rpm"));
+        assert_eq!(metadata.name.as_deref(), Some("bash"));
+        assert_eq!(metadata.version.as_deref(), Some("5.2.26-3.fc40"));
+        assert_eq!(metadata.architecture.as_deref(), Some("x86_64"));
+        assert_eq!(metadata.os, None);
+        assert_eq!(metadata.debug_info_url, None);
+    }
+}
+
+#[test]
+fn package_metadata_invalid() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let end = write_note(
+        &mut elf_builder,
+        0x800,
+        b"FDO\0",
+        0xCAFE1A7E,
+        b"{\"name\":1}\0",
+    );
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let metadata = elf.package_metadata().unwrap();
+    assert_eq!(metadata.json, "{\"name\":1}");
+    #[cfg(feature = "serde")]
+    assert_eq!(metadata.name, None);
+
+    let elf_builder = ElfBuilder64::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.package_metadata(), None);
+}
+
+fn android_builder(ident: bool) -> ElfBuilder64 {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let mut desc = 29_u32.to_le_bytes().to_vec();
+    let mut ndk_version = b"r26b".to_vec();
+    ndk_version.resize(64, 0);
+    desc.extend_from_slice(&ndk_version);
+    let mut ndk_build_number = b"10909125".to_vec();
+    ndk_build_number.resize(64, 0);
+    desc.extend_from_slice(&ndk_build_number);
+    let end = write_note(
+        &mut elf_builder,
+        0x800,
+        if ident { b"Android\0" } else { b"Other\0\0\0" },
+        1,
+        &desc,
+    );
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+
+    let entries: [(u64, u64); 4] = [
+        (AndroidDynamic::DT_ANDROID_RELR, 0x1234),
+        (30, 0x08),
+        (AndroidDynamic::DT_ANDROID_RELRSZ, 0x10),
+        (0, 0),
+    ];
+    for (i, (tag, value)) in entries.iter().enumerate() {
+        elf_builder.write_u64(0xA00 + i * 16, *tag);
+        elf_builder.write_u64(0xA00 + i * 16 + 8, *value);
+    }
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Dynamic,
+        flags: SegmentFlags::from(SegmentFlags::R | SegmentFlags::W),
+        file_offset: 0xA00,
+        virtual_address: 0xA00,
+        physical_address: 0xA00,
+        file_size: 0x40,
+        memory_size: 0x40,
+        alignment: 8,
+    });
+    elf_builder
+}
+
+#[test]
+fn android_ident() {
+    let elf_builder = android_builder(true);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        elf.android_ident(),
+        Some(AndroidIdent {
+            api_level: 29,
+            ndk_version: Some("r26b".to_string()),
+            ndk_build_number: Some("10909125".to_string()),
+        })
+    );
+    assert_eq!(
+        elf.android_dynamic(),
+        vec![
+            AndroidDynamic {
+                tag: AndroidDynamic::DT_ANDROID_RELR,
+                value: 0x1234,
+            },
+            AndroidDynamic {
+                tag: AndroidDynamic::DT_ANDROID_RELRSZ,
+                value: 0x10,
+            },
+        ]
+    );
+    assert_eq!(elf.android_dynamic()[0].name(), "ANDROID_RELR");
+}
+
+#[test]
+fn android_ident_api_level_only() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Big);
+    let end = write_note(&mut elf_builder, 0x800, b"Android\0", 1, &[0, 0, 0, 21]);
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        elf.android_ident(),
+        Some(AndroidIdent {
+            api_level: 21,
+            ndk_version: None,
+            ndk_build_number: None,
+        })
+    );
+}
+
+#[test]
+fn android_not_android() {
+    let elf_builder = android_builder(false);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.android_ident(), None);
+    assert_eq!(elf.android_dynamic(), vec![]);
+}
+
+#[test]
+fn stack_flags() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_executable_type(ExecutableType::Dynamic);
+    elf_builder.add_segment(&segment(
+        SegmentType::from(0x6474E551),
+        SegmentFlags::R | SegmentFlags::W,
+        0,
+        0,
+        0,
+        0,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(
+        elf.stack_flags(),
+        Some(SegmentFlags::from(SegmentFlags::R | SegmentFlags::W))
+    );
+    assert!(!elf.is_stack_executable());
+
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_executable_type(ExecutableType::Dynamic);
+    elf_builder.add_segment(&segment(
+        SegmentType::from(0x6474E551),
+        SegmentFlags::R | SegmentFlags::W | SegmentFlags::X,
+        0,
+        0,
+        0,
+        0,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert!(elf.is_stack_executable());
+}
+
+#[test]
+fn stack_flags_implicit() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_machine(Machine::from(Machine::INTEL_386));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.stack_flags(), None);
+    assert!(elf.is_stack_executable());
+
+    elf_builder.set_machine(Machine::from(Machine::AARCH64));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert!(!elf.is_stack_executable());
+
+    elf_builder
+        .set_machine(Machine::from(Machine::INTEL_386))
+        .set_executable_type(ExecutableType::Relocatable);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert!(!elf.is_stack_executable());
+}
+
+#[test]
+fn load_bias() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Dynamic)
+        .set_entry(0x1040);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1040,
+        0x1040,
+        0x100,
+        0x100,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x3000,
+        0x3000,
+        0x100,
+        0x100,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let base = 0x7F00_0000_0000;
+    assert_eq!(elf.image_base(), Some(0x1000));
+    assert_eq!(elf.load_bias(base), Some(0x7EFF_FFFF_F000));
+    assert_eq!(elf.runtime_address(elf.entry, base), Some(0x7F00_0000_0040));
+    assert_eq!(elf.file_address(0x7F00_0000_2010, base), Some(0x3010));
+
+    let bias = elf.load_bias(base).unwrap();
+    let ph = elf.program_headers().nth(1).unwrap().rebased(bias);
+    assert_eq!(ph.virtual_address, 0x7F00_0000_2000);
+    assert_eq!(ph.physical_address, 0x3000);
+    assert_eq!(ph.file_offset, 0x3000);
+}
+
+#[test]
+fn load_bias_negative() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x8000,
+        0x8048000,
+        0x100,
+        0x100,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let bias = elf.load_bias(0x1000).unwrap();
+    assert_eq!(bias, 0x1000_u64.wrapping_sub(0x8048000));
+    assert_eq!(elf.runtime_address(0x8048010, 0x1000), Some(0x1010));
+    assert_eq!(elf.file_address(0x1010, 0x1000), Some(0x8048010));
+}
+
+#[test]
+fn load_bias_no_segments() {
+    let elf_builder = ElfBuilder64::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.image_base(), None);
+    assert_eq!(elf.load_bias(0x1000), None);
+    assert_eq!(elf.runtime_address(0x10, 0x1000), None);
+}
+
+#[test]
+fn entry_segment() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_entry(0x401020);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x0,
+        0x400000,
+        0x100,
+        0x100,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x401000,
+        0x100,
+        0x100,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x3000,
+        0x403000,
+        0x100,
+        0x100,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.entry_segment().unwrap().virtual_address, 0x401000);
+    assert!(elf.is_entry_executable());
+
+    elf_builder.set_entry(0x403010);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.entry_segment().unwrap().virtual_address, 0x403000);
+    assert!(!elf.is_entry_executable());
+
+    elf_builder.set_entry(0x500000);
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+    assert_eq!(elf.entry_segment(), None);
+    assert!(!elf.is_entry_executable());
+}
+
+#[test]
+fn segment_gaps() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x0,
+        0x400000,
+        0x300,
+        0x300,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x100,
+        0x100,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x1080,
+        0x401080,
+        0x10,
+        0x10,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1800,
+        0x401800,
+        0x10,
+        0x800,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::from(0x6474E551),
+        SegmentFlags::R | SegmentFlags::W,
+        0,
+        0,
+        0,
+        0,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.segment_gaps(), vec![0x300..0x1000, 0x1100..0x1800]);
+}
+
+#[test]
+fn segment_gaps_no_segments() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert_eq!(elf.segment_gaps(), vec![]);
+}
+
+#[test]
+fn misaligned_segments() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.set_machine(Machine::from(Machine::AARCH64));
+    elf_builder.add_segment(&ProgramHeader {
+        alignment: 0x10000,
+        ..segment(SegmentType::Load, SegmentFlags::R, 0x0, 0x0, 0x100, 0x100)
+    });
+    elf_builder.add_segment(&ProgramHeader {
+        alignment: 0x10000,
+        ..segment(
+            SegmentType::Load,
+            SegmentFlags::R,
+            0x1000,
+            0x11000,
+            0x100,
+            0x100,
+        )
+    });
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1100,
+        0x21100,
+        0x100,
+        0x100,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::from(0x6474E551),
+        SegmentFlags::R | SegmentFlags::W,
+        0,
+        0,
+        0,
+        0,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let page_size = elf.machine.max_page_size().unwrap();
+    assert_eq!(page_size, 0x10000);
+    let misaligned: Vec<usize> = elf
+        .misaligned_segments(page_size)
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(misaligned, vec![2]);
+    assert_eq!(elf.misaligned_segments(0x1000), vec![]);
+    assert_eq!(elf.misaligned_segments(0x1001).len(), 3);
+}
+
+#[test]
+fn reproducibility_report() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let end = write_note(&mut elf_builder, 0x800, b"GNU\0", 3, &[0xAB; 20]);
+    let size = (end - 0x800) as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Note,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        size,
+        size,
+    ));
+    let strings: &[u8] = b"usage: %s FILE\0/home/ci/src/main.c\0Built Oct 16 2026 12:00:00\0";
+    for (i, b) in strings.iter().enumerate() {
+        elf_builder.write_u8(0x1000 + i, *b);
+    }
+    let size = strings.len() as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x1000,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.build_id(), Some(vec![0xAB; 20]));
+    let report = elf.reproducibility_report();
+    assert_eq!(report.build_id, elf.build_id());
+    assert_eq!(
+        report.build_paths,
+        vec![(0x100F, "/home/ci/src/main.c".to_string())]
+    );
+    assert_eq!(
+        report.timestamps,
+        vec![(0x1023, "Built Oct 16 2026 12:00:00".to_string())]
+    );
+    assert!(!report.is_reproducible());
+}
+
+#[test]
+fn reproducibility_report_writable() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let strings: &[u8] = b"/home/ci/src/main.c\0";
+    for (i, b) in strings.iter().enumerate() {
+        elf_builder.write_u8(0x1000 + i, *b);
+    }
+    let size = strings.len() as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x1000,
+        0x1000,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.reproducibility_report();
+    assert_eq!(report, ReproducibilityReport::default());
+    assert!(report.is_reproducible());
+}
+
+#[test]
+fn embedded_paths() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    let strings: &[u8] = b"usage: %s FILE\0cannot open /etc/app.conf\0/usr/lib:/opt/lib\0";
+    for (i, b) in strings.iter().enumerate() {
+        elf_builder.write_u8(0x1000 + i, *b);
+    }
+    let size = strings.len() as u64;
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x1000,
+        size,
+        size,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        elf.embedded_paths(),
+        vec![
+            (0x101B, "/etc/app.conf".to_string()),
+            (0x1029, "/usr/lib".to_string()),
+            (0x1032, "/opt/lib".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn embedded_paths_truncated() {
+    // The segment is far larger than the file, and isn't read.
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x1000,
+        1 << 36,
+        1 << 36,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    assert!(elf.embedded_paths().is_empty());
+    assert!(elf.reproducibility_report().build_paths.is_empty());
+}
+
+#[test]
+fn packer_report() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_entry(0x400800);
+
+    // The contents are pseudo random, like compressed data, with the magic of
+    // UPX.
+    let mut x = 0x12345678_u32;
+    for i in 0x800..0x1800 {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        elf_builder.write_u8(i, x as u8);
+    }
+    for (i, b) in b"UPX!".iter().enumerate() {
+        elf_builder.write_u8(0x900 + i, *b);
+    }
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0,
+        0x100000,
+        0,
+        0x200000,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W | SegmentFlags::X,
+        0x800,
+        0x400800,
+        0x1000,
+        0x1000,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.packer_report();
+    assert_eq!(report.indicators.len(), 6);
+    assert_eq!(report.indicators[0], PackerIndicator::UpxMagic(0x900));
+    assert_eq!(report.indicators[1], PackerIndicator::NoSections);
+    assert_eq!(report.indicators[2], PackerIndicator::WritableExecutable(1));
+    assert_eq!(report.indicators[3], PackerIndicator::ExpandedSegment(0));
+    assert_eq!(report.indicators[4], PackerIndicator::SuspiciousEntry);
+    assert!(matches!(
+        report.indicators[5],
+        PackerIndicator::HighEntropy(1, e) if e > 7.9
+    ));
+    assert_eq!(report.confidence, 100);
+    assert!(report.is_packed());
+
+    // Segments larger than the limit aren't read.
+    let options = ReadOptions {
+        max_allocation: 0x800,
+        ..ReadOptions::default()
+    };
+    let elf = ReadElf::from_slice_with_options(elf_builder.buffer(), options).unwrap();
+    let report = elf.packer_report();
+    assert_eq!(report.indicators.len(), 4);
+    assert_eq!(report.indicators[0], PackerIndicator::NoSections);
+}
+
+#[test]
+fn packer_report_not_packed() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_entry(0x1000);
+    // The number of sections is in the first section header (extended
+    // numbering), with `e_shnum` of zero.
+    elf_builder.write_u64(0x28, 0x1800);
+    elf_builder.write_u16(0x3A, 64);
+    elf_builder.write_u64(0x1800 + 32, 70000);
+    for i in 0x1000..0x1400 {
+        elf_builder.write_u8(i, (i % 16) as u8);
+    }
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x1000,
+        0x400,
+        0x400,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.packer_report();
+    assert_eq!(report, PackerReport::default());
+    assert!(!report.is_packed());
+}
+
+#[test]
+fn find_bytes() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    for (offset, bytes) in [
+        (0x1000, &[0xE8, 0x10, 0x20, 0x30][..]),
+        (0x1100, &[0xE8, 0x11, 0x21, 0x31]),
+        (0x1200, &[0xE9, 0x10, 0x20, 0x30]),
+        (0x1400, &[0xE8, 0x12, 0x22, 0x32]),
+    ] {
+        for (i, b) in bytes.iter().enumerate() {
+            elf_builder.write_u8(offset + i, *b);
+        }
+    }
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x401000,
+        0x300,
+        0x300,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1400,
+        0x402400,
+        0x100,
+        0x100,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(
+        elf.find_bytes(&[0xE8, 0x10], None),
+        Some(vec![(0x1000, 0x401000)])
+    );
+    assert_eq!(
+        elf.find_bytes(&[0xE8, 0x10, 0x20], Some(&[0xFF, 0xF0, 0xF0])),
+        Some(vec![
+            (0x1000, 0x401000),
+            (0x1100, 0x401100),
+            (0x1400, 0x402400)
+        ])
+    );
+
+    let ph = elf.program_headers().nth(1).unwrap();
+    assert_eq!(
+        elf.find_bytes_in(&ph, &[0x12, 0x22], None),
+        Some(vec![(0x1401, 0x402401)])
+    );
+    assert_eq!(elf.find_bytes(&[], None), None);
+    assert_eq!(elf.find_bytes(&[0xE8], Some(&[])), None);
+}
+
+#[test]
+fn find_bytes_chunks() {
+    // A match across the boundary of the chunks read from the file.
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x1000,
+        0x20000,
+        0x20000,
+    ));
+    let mut buffer = vec![0; 0x30000];
+    buffer[..0x800].copy_from_slice(&elf_builder.buffer()[..0x800]);
+    buffer[0x10FFE..0x11002].copy_from_slice(b"ABCD");
+    buffer[0x20FFC..0x21000].copy_from_slice(b"ABCD");
+    let elf = ReadElf::from_vec(buffer).unwrap();
+
+    assert_eq!(
+        elf.find_bytes(b"ABCD", None),
+        Some(vec![(0x10FFE, 0x10FFE), (0x20FFC, 0x20FFC)])
+    );
+    // The segment is truncated in the middle of the second chunk, which is
+    // searched up to the end of the file.
+    let mut buffer = vec![0; 0x18000];
+    buffer[..0x800].copy_from_slice(&elf_builder.buffer()[..0x800]);
+    buffer[0x17FF0..0x17FF4].copy_from_slice(b"ABCD");
+    let elf = ReadElf::from_vec(buffer).unwrap();
+    assert_eq!(
+        elf.find_bytes(b"ABCD", None),
+        Some(vec![(0x17FF0, 0x17FF0)])
+    );
+}
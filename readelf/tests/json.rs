@@ -0,0 +1,68 @@
+//! Test suite for serializing the ELF file as JSON and YAML.
+#![cfg(feature = "serde")]
+
+use readelf::*;
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder64};
+
+#[test]
+fn to_json() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Dynamic)
+        .set_machine(Machine::from(Machine::X86_64))
+        .set_entry(0x1040);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R | SegmentFlags::X),
+        file_offset: 0x1000,
+        virtual_address: 0x1000,
+        physical_address: 0x1000,
+        file_size: 0x200,
+        memory_size: 0x200,
+        alignment: 0x1000,
+    });
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&elf.to_json()).unwrap();
+    assert_eq!(json["header"]["class"]["value"], 2);
+    assert_eq!(json["header"]["class"]["name"], "64-bit ELF");
+    assert_eq!(json["header"]["exec_type"]["value"], 3);
+    assert_eq!(json["header"]["machine"]["value"], Machine::X86_64);
+    assert_eq!(json["header"]["entry"], 0x1040);
+    assert_eq!(json["header"]["program_header_count"], 1);
+
+    let segments = json["segments"].as_array().unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0]["index"], 0);
+    assert_eq!(segments[0]["segment_type"]["value"], 1);
+    assert_eq!(segments[0]["flags"]["value"], 5);
+    assert_eq!(segments[0]["file_size"], 0x200);
+}
+
+#[test]
+fn to_yaml() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder
+        .set_executable_type(ExecutableType::Executable)
+        .set_machine(Machine::from(Machine::AARCH64))
+        .set_entry(0x400000);
+    elf_builder.add_segment(&ProgramHeader {
+        segment_type: SegmentType::Load,
+        flags: SegmentFlags::from(SegmentFlags::R),
+        file_offset: 0,
+        virtual_address: 0x400000,
+        physical_address: 0x400000,
+        file_size: 0x100,
+        memory_size: 0x100,
+        alignment: 0x10000,
+    });
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    // The same document as the JSON output.
+    let yaml: serde_json::Value = serde_yaml_ng::from_str(&elf.to_yaml()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&elf.to_json()).unwrap();
+    assert_eq!(yaml, json);
+    assert_eq!(yaml["header"]["machine"]["name"], "ARM 64-bit");
+}
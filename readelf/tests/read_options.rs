@@ -0,0 +1,99 @@
+//! Test suite for the limits when reading untrusted ELF files.
+
+use readelf::*;
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder32, ElfBuilder64};
+use common::segment::segment;
+
+#[test]
+fn default_options() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    assert_eq!(elf.options(), ReadOptions::default());
+    assert_eq!(elf.options().max_program_headers, u32::MAX);
+    assert_eq!(elf.options().max_section_headers, u64::MAX);
+    assert_eq!(elf.options().max_allocation, u64::MAX);
+}
+
+#[test]
+fn program_header_limit() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    for _ in 0..3 {
+        elf_builder.add_segment(&segment(
+            SegmentType::Load,
+            SegmentFlags::R,
+            0,
+            0,
+            0x100,
+            0x100,
+        ));
+    }
+    let options = ReadOptions {
+        max_program_headers: 2,
+        ..Default::default()
+    };
+
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let err = elf.with_options(options).unwrap_err();
+    assert_eq!(err, ParseError::ProgramHeaderLimit(3));
+
+    let options = ReadOptions {
+        max_program_headers: 3,
+        ..Default::default()
+    };
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let elf = elf.with_options(options).unwrap();
+    assert_eq!(elf.options(), options);
+    assert_eq!(elf.program_headers().count(), 3);
+}
+
+#[test]
+fn section_header_limit() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Big);
+    elf_builder.write_u64(0x28, 0x400); // e_shoff
+    elf_builder.write_u16(0x3A, 64); // e_shentsize
+    elf_builder.write_u16(0x3C, 0); // e_shnum
+    elf_builder.write_u64(0x400 + 32, 0x12345); // sh_size of section 0
+    let options = ReadOptions {
+        max_section_headers: 0xFFFF,
+        ..Default::default()
+    };
+
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let err = elf.with_options(options).unwrap_err();
+    assert_eq!(err, ParseError::SectionHeaderLimit(0x12345));
+}
+
+#[test]
+fn allocation_limit() {
+    let mut elf_builder = ElfBuilder64::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Interpreter,
+        SegmentFlags::R,
+        0x800,
+        0x800,
+        12,
+        12,
+    ));
+    for (i, b) in b"/lib/ld.so\0".iter().enumerate() {
+        elf_builder.write_u8(0x800 + i, *b);
+    }
+
+    let options = ReadOptions {
+        max_allocation: 12,
+        ..Default::default()
+    };
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let elf = elf.with_options(options).unwrap();
+    assert_eq!(elf.interpreter().as_deref(), Some("/lib/ld.so"));
+
+    let options = ReadOptions {
+        max_allocation: 11,
+        ..Default::default()
+    };
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+    let elf = elf.with_options(options).unwrap();
+    assert_eq!(elf.interpreter(), None);
+}
@@ -0,0 +1,168 @@
+//! Test suite calculating the sizes of the loadable segments.
+
+use readelf::*;
+
+mod common;
+use common::builder::{ElfBuilder, ElfBuilder32};
+use common::segment::segment;
+
+#[test]
+fn size_report_no_segments() {
+    let elf_builder = ElfBuilder32::new(Endian::Little);
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.size_report();
+    assert_eq!(report, SizeReport::default());
+    assert_eq!(report.total(), 0);
+}
+
+#[test]
+fn size_report_text_data_bss() {
+    let rx = SegmentFlags::R + SegmentFlags::X;
+    let rw = SegmentFlags::R + SegmentFlags::W;
+
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::ProgramHeader,
+        SegmentFlags::R,
+        0x1000,
+        0x1000,
+        0x40,
+        0x40,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        rx,
+        0x1000,
+        0x1000,
+        0x1000,
+        0x1000,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R,
+        0x1000,
+        0x1000,
+        0x200,
+        0x200,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        rw,
+        0x1000,
+        0x1000,
+        0x100,
+        0x180,
+    ));
+    elf_builder.add_segment(&segment(SegmentType::Load, rx, 0x1000, 0x1000, 0x20, 0x20));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.size_report();
+    assert_eq!(report.text, 0x1220);
+    assert_eq!(report.data, 0x100);
+    assert_eq!(report.bss, 0x80);
+    assert_eq!(report.total(), 0x13A0);
+
+    assert_eq!(report.segments.len(), 4);
+    assert_eq!(report.segments[0].index, 1);
+    assert_eq!(report.segments[3].index, 4);
+
+    assert_eq!(
+        report.by_flags(),
+        vec![
+            (SegmentFlags::from(rx), 0x1020, 0x1020),
+            (SegmentFlags::from(SegmentFlags::R), 0x200, 0x200),
+            (SegmentFlags::from(rw), 0x100, 0x180),
+        ]
+    );
+}
+
+#[test]
+fn size_report_memory_smaller_than_file() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R + SegmentFlags::W,
+        0x1000,
+        0x1000,
+        0x100,
+        0x10,
+    ));
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.size_report();
+    assert_eq!(report.data, 0x100);
+    assert_eq!(report.bss, 0);
+}
+
+#[test]
+fn format_size() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x1000,
+        0x1000,
+        0x1000,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x1000,
+        0x1000,
+        0x100,
+        0x300,
+    ));
+    let elf = ReadElf::from_vec(elf_builder.buffer().to_vec()).unwrap();
+
+    assert_eq!(
+        display::SIZE_HEADER,
+        "   text\t   data\t    bss\t    dec\t    hex\tfilename"
+    );
+    assert_eq!(
+        display::format_size(&elf.size_report(), "a.out"),
+        "   4096\t    256\t    512\t   4864\t   1300\ta.out"
+    );
+}
+
+#[test]
+fn memory_footprint() {
+    let mut elf_builder = ElfBuilder32::new(Endian::Little);
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::X,
+        0x1000,
+        0x10000,
+        0x1234,
+        0x1234,
+    ));
+    elf_builder.add_segment(&segment(
+        SegmentType::Load,
+        SegmentFlags::R | SegmentFlags::W,
+        0x1000,
+        0x21F00,
+        0x100,
+        0x300,
+    ));
+    elf_builder.add_segment(&ProgramHeader {
+        alignment: 0,
+        ..segment(
+            SegmentType::Load,
+            SegmentFlags::R,
+            0x1000,
+            0x30010,
+            0x10,
+            0x10,
+        )
+    });
+    let elf = ReadElf::from_slice(elf_builder.buffer()).unwrap();
+
+    let report = elf.size_report();
+    assert_eq!(report.segments[0].virtual_address, 0x10000);
+    assert_eq!(report.segments[0].alignment, 0x1000);
+    assert_eq!(report.segments[0].memory_footprint(), 0x2000);
+    assert_eq!(report.segments[1].memory_footprint(), 0x2000);
+    assert_eq!(report.segments[2].memory_footprint(), 0x10);
+    assert_eq!(report.memory_footprint(), 0x4010);
+}